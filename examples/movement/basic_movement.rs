@@ -6,9 +6,15 @@ fn main() {
     let mut app = App::new();
   app
     .add_plugins(DefaultPlugins)
-    .add_plugin(FighterPlugin);
+    .add_plugin(FighterPlugin)
+    .add_plugin(FighterHudPlugin)
+    .add_plugin(CollisionViewPlugin);
   app
-    .add_startup_system(setup.after(FighterSystemLabels::InitializeCharacterData))
+    .add_startup_system(
+      setup
+        .after(FighterSystemLabels::InitializeCharacterData)
+        .after(FighterSystemLabels::InitializeStageData)
+    )
     .add_system(set_camera_scale)
     .run();
 }
@@ -18,6 +24,9 @@ fn main() {
 fn setup(
     mut coms: Commands,
     character_library: Res<CharacterLibrary>,
+    round_config: Res<RoundConfig>,
+    stage_library: Res<StageLibrary>,
+    asset_server: Res<AssetServer>,
 ) {
     coms
         .spawn_bundle(OrthographicCameraBundle::new_2d())
@@ -25,10 +34,11 @@ fn setup(
             ..Default::default()
         });
     coms.spawn_bundle(UiCameraBundle::default());
+    coms.spawn_stage(stage_library.get_stage("training").unwrap(), &asset_server);
     coms.spawn_bundle(
-        FighterCharacterBundle::new(PlayerId::P1, "roa", &character_library)
+        FighterCharacterBundle::new(PlayerId::P1, "roa", &character_library, &round_config)
     );
     coms.spawn_bundle(
-        FighterCharacterBundle::new(PlayerId::P2,"aoko", &character_library)
+        FighterCharacterBundle::new(PlayerId::P2,"aoko", &character_library, &round_config)
     );
 }
@@ -5,7 +5,10 @@ use crate::{
     AnimationSerialized,
     CharacterLibrary
   },
-  character::PlayerId
+  character::{
+    CharacterState,
+    PlayerId
+  }
 };
 
 use std::collections::HashMap;
@@ -85,10 +88,28 @@ pub enum AnimationTransition {
   ToBackwalk,
   ToDash,
   ToBackdash,
+  ToBackTeleport,
+  ToBackLeap,
+  BackLeapToLand,
+  ToLandingRecovery,
   ToRise,
+  ToSuperJump,
   ToIdle,
   ToAirdash,
   ToAirBackdash,
+  ToAirJump,
+  ToHitstun,
+  ToBlockstun,
+  ToJuggle,
+  ToAirTech,
+  AirTechToFall,
+  ToSoftKnockdown,
+  ToHardKnockdown,
+  ToWakeup,
+  ToQuickRise,
+  ToThrown,
+  ToThrowTech,
+  ToThrow {name: String},
   ToAttack {name: String},
 }
 
@@ -199,12 +220,46 @@ impl AnimationController {
 
       ToAttack {name} => self.loop_animation(name),
 
+      ToHitstun => self.loop_animation("hitstun".into()),
+
+      ToBlockstun => self.loop_animation("blockstun".into()),
+
+      ToJuggle => self.loop_animation("juggle".into()),
+
+      ToAirTech => self.loop_animation("airtech".into()),
+
+      AirTechToFall => self.smear_animation("airtech<>fall".into(), "fall".into()),
+
+      ToSoftKnockdown => self.smear_animation("juggle<>knockdown".into(), "knockdown".into()),
+
+      ToHardKnockdown => self.smear_animation("juggle<>hardknockdown".into(), "hardknockdown".into()),
+
+      ToWakeup => self.loop_animation("wakeup".into()),
+
+      ToQuickRise => self.loop_animation("quickrise".into()),
+
+      ToThrow {name} => self.loop_animation(format!("{}_throw", name)),
+
+      ToThrown => self.loop_animation("thrown".into()),
+
+      ToThrowTech => self.loop_animation("throwtech".into()),
+
       ToDash => self.smear_animation("idle<>dash".into(), "dash".into()),
 
       ToBackdash => self.smear_animation("idle<>backdash".into(), "backdash".into()),
 
+      ToBackTeleport => self.smear_animation("idle<>backteleport".into(), "backteleport".into()),
+
+      ToBackLeap => self.smear_animation("idle<>backleap".into(), "backleap".into()),
+
+      BackLeapToLand => self.smear_animation("backleap<>land".into(), "idle".into()),
+
+      ToLandingRecovery => self.smear_animation("fall<>land".into(), "land".into()),
+
       ToRise => self.smear_animation("jumpsquat".into(), "rise".into()),
 
+      ToSuperJump => self.smear_animation("superjumpsquat".into(), "rise".into()),
+
       ToWalk => self.smear_animation("idle<>walk".into(), "walk".into()),
 
       ToBackwalk => self.smear_animation("idle<>backwalk".into(), "backwalk".into()),
@@ -223,6 +278,8 @@ impl AnimationController {
 
       ToAirBackdash => self.smear_animation("fall<>backairdash".into(), "backairdash".into()),
 
+      ToAirJump => self.smear_animation("airjumpsquat".into(), "rise".into()),
+
       RiseToFall => self.smear_animation("rise<>fall".into(), "fall".into()),
 
       FallToIdle => self.smear_animation("fall<>idle".into(), "idle".into()),
@@ -236,9 +293,14 @@ impl AnimationController {
     self.reset();
   }
 
+  /// Smears are optional, a character without the smear animation for a transition goes straight to looping it's core animation
   fn update_animation(&mut self, state: AnimationState, smear: Option<String>, core: String) {
-    self.animation_state = state;
-    self.smear_animation = smear.map(|s| self.get_animation(s).unwrap());
+    self.smear_animation = smear.and_then(|s| self.get_animation(s));
+    self.animation_state = if self.smear_animation.is_some() {
+      state
+    } else {
+      AnimationState::LOOPING
+    };
     let core_animation = self.get_animation(core);
 
     if let Some(ca) = core_animation {
@@ -246,6 +308,12 @@ impl AnimationController {
     }
   }
   
+  /// Loop an animation outside of a transition, used by entities that don't change state like projectiles
+  pub fn play(&mut self, animation: String) {
+    self.loop_animation(animation);
+    self.reset();
+  }
+
   fn loop_animation(&mut self, animation: String) {
     self.update_animation(AnimationState::LOOPING, None, animation);
   }
@@ -257,7 +325,7 @@ impl AnimationController {
 
 #[doc(hidden)]
 pub fn read_animation_transitions(
-  mut query: Query<(&PlayerId, &mut AnimationController)>,
+  mut query: Query<(&PlayerId, &mut AnimationController), With<CharacterState>>,
   mut transition_reader: EventReader<AnimationTransitionEvent>,
 ) {
   for event in transition_reader.iter() {
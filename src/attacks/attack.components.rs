@@ -1,10 +1,17 @@
+use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::{
   character_library::CharacterLibrary,
+  constants::SUPERFLASH_DURATION,
   collision::{
+    CounterHit,
     HitboxEvent,
-    HitboxEventSerialized
-  }
+    HitboxEventSerialized,
+    HitState,
+    Vec2Serialzed
+  },
+  inputs::CommandType,
+  physics::InterpolatedForce
 };
 
 
@@ -18,6 +25,45 @@ pub struct Attack {
   pub hitbox_events: Vec<HitboxEvent>,
   /// how long the attack will take to complete
   pub busy: u8,
+  /// grab performed by the attack, if it is a throw
+  pub throw: Option<Throw>,
+  /// command motion required to perform the attack, if it is a special move
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub command: Option<CommandType>,
+  /// what kind of move the attack is, used by cancel routes
+  pub category: AttackCategory,
+  /// can the attack be performed on the ground, in the air, or both
+  pub stance: AttackStance,
+  /// frames spent landing if the attack whiffs in the air, overriding the character's default
+  pub landing_recovery: Option<u8>,
+  /// moments throughout the attack when the attacker is moved
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub movement_events: Vec<AttackMovement>,
+  /// what the attack can be cancelled into, and when
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub cancels: Vec<CancelRoute>,
+  /// frames the attacker can't be hit by strikes
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub strike_invulnerable: Option<FrameRange>,
+  /// frames the attacker can't be thrown
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub throw_invulnerable: Option<FrameRange>,
+  /// frames the attacker can't be hit by projectiles
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub projectile_invulnerable: Option<FrameRange>,
+  /// frames the attacker absorbs hits, and how many hits are left to absorb
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub armor: Option<Armor>,
+  /// frames where getting hit is a counter hit, the startup of the attack
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub counter_hit: Option<FrameRange>,
+  /// frames where getting hit is a punish counter, the recovery of the attack
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub punish_counter: Option<FrameRange>,
+  /// meter spent to perform the attack
+  pub meter_cost: u16,
+  /// frames gameplay freezes for when the attack starts
+  pub superflash: Option<u8>,
 }
 impl Attack {
   /// Create an attack from it's serialized counterpart
@@ -27,10 +73,309 @@ impl Attack {
     for s_he in s.hitbox_events {
       hitbox_events.push(HitboxEvent::from_serialized(s_he, library, character_name));
     }
+    let throw = s.throw.map(Throw::from_serialized);
+
+    // Counter hit frames default to the startup, punish counters to the recovery
+    let active = active_frames(&hitbox_events, &throw);
+    let counter_hit = s.counter_hit.or(
+      active
+        .filter(|a| a.start > 0)
+        .map(|a| FrameRange {start: 0, end: a.start - 1})
+    );
+    let punish_counter = s.punish_counter.or(
+      active
+        .filter(|a| a.end < s.busy)
+        .map(|a| FrameRange {start: a.end + 1, end: s.busy})
+    );
+
+    let category = s.category.unwrap_or(if s.command.is_some() {AttackCategory::Special} else {AttackCategory::Normal});
+    // Supers always flash, other attacks only when they ask to
+    let superflash = match category {
+      AttackCategory::Super => Some(s.superflash.unwrap_or(SUPERFLASH_DURATION)),
+      _ => s.superflash
+    };
+
     Attack {
       name: s.name,
       hitbox_events,
       busy: s.busy,
+      throw,
+      category,
+      command: s.command,
+      stance: s.stance,
+      landing_recovery: s.landing_recovery,
+      movement_events: s.movement_events.into_iter().map(AttackMovement::from_serialized).collect(),
+      cancels: s.cancels,
+      strike_invulnerable: s.strike_invulnerable,
+      throw_invulnerable: s.throw_invulnerable,
+      projectile_invulnerable: s.projectile_invulnerable,
+      armor: s.armor,
+      counter_hit,
+      punish_counter,
+      meter_cost: s.meter_cost,
+      superflash,
+    }
+  }
+
+  pub fn is_strike_invulnerable(&self, frame: u8) -> bool {
+    in_range(&self.strike_invulnerable, frame)
+  }
+
+  pub fn is_throw_invulnerable(&self, frame: u8) -> bool {
+    in_range(&self.throw_invulnerable, frame)
+  }
+
+  pub fn is_projectile_invulnerable(&self, frame: u8) -> bool {
+    in_range(&self.projectile_invulnerable, frame)
+  }
+
+  /// Does the attack have armor left on this frame
+  pub fn is_armored(&self, frame: u8) -> bool {
+    match self.armor {
+      Some(armor) => armor.hits > 0 && armor.window.contains(frame),
+      None => false
+    }
+  }
+
+  /// Use up one hit of the attack's armor
+  pub fn absorb_hit(&mut self) {
+    if let Some(armor) = self.armor.as_mut() {
+      armor.hits = armor.hits.saturating_sub(1);
+    }
+  }
+
+  /// Returns what kind of counter hit getting hit on this frame would be
+  pub fn get_counter_hit(&self, frame: u8) -> Option<CounterHit> {
+    if in_range(&self.counter_hit, frame) {
+      return Some(CounterHit::Counter);
+    }
+    if in_range(&self.punish_counter, frame) {
+      return Some(CounterHit::Punish);
+    }
+    return None;
+  }
+
+  /// Returns how many frames of the attack have elapsed, given the frames remaining
+  pub fn current_frame(&self, duration: u8) -> u8 {
+    self.busy.saturating_sub(duration)
+  }
+
+  /// Returns the cancel routes that are open on the given frame of the attack
+  pub fn open_cancels(&self, frame: u8, hit_state: HitState) -> Vec<CancelRoute> {
+    self.cancels
+      .iter()
+      .filter(|route| route.is_open(frame, hit_state))
+      .cloned()
+      .collect()
+  }
+}
+
+fn in_range(range: &Option<FrameRange>, frame: u8) -> bool {
+  match range {
+    Some(r) => r.contains(frame),
+    None => false
+  }
+}
+
+/// Returns the frames the attack's hitboxes and throwbox are out
+fn active_frames(hitbox_events: &Vec<HitboxEvent>, throw: &Option<Throw>) -> Option<FrameRange> {
+  let mut ranges: Vec<(u8, u8)> = hitbox_events
+    .iter()
+    .map(|e| (e.frame, e.frame.saturating_add(e.hitbox.get_duration().saturating_sub(1))))
+    .collect();
+  if let Some(t) = throw {
+    ranges.push((t.frame, t.frame.saturating_add(t.active.saturating_sub(1))));
+  }
+  let start = ranges.iter().map(|r| r.0).min()?;
+  let end = ranges.iter().map(|r| r.1).max()?;
+  return Some(FrameRange {start, end});
+}
+
+/// Hits an attack can absorb during a window of frames
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Armor {
+  pub window: FrameRange,
+  pub hits: u8,
+}
+
+/// Broad kinds of attacks, so cancel routes can target groups of moves
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttackCategory {
+  Normal,
+  Special,
+  Super,
+}
+
+impl Default for AttackCategory {
+  fn default() -> Self {
+    AttackCategory::Normal
+  }
+}
+
+/// Force applied to the attacker during an attack, relative to the direction they are facing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AttackMovement {
+  /// what frame of the attack the force is applied
+  pub frame: u8,
+  /// velocity of the force when it's applied
+  pub start: Vec2,
+  /// velocity the force eases to over it's duration
+  pub end: Vec2,
+  /// how many frames the force lasts
+  pub duration: u8,
+  /// should the attacker's current momentum be cancelled when the force is applied
+  pub reset_velocity: bool,
+}
+
+impl AttackMovement {
+  /// Create an attack movement from it's serialized counterpart
+  pub fn from_serialized(s: AttackMovementSerialized) -> Self {
+    AttackMovement {
+      frame: s.frame,
+      start: s.start.to_vec2(),
+      end: s.end.to_vec2(),
+      duration: s.duration.max(1),
+      reset_velocity: s.reset_velocity,
+    }
+  }
+
+  /// Returns the interpolated force for this movement, facing the passed direction
+  pub fn to_interpolated_force(&self, facing_vector: f32) -> InterpolatedForce {
+    let facing = Vec2::new(facing_vector, 1.0);
+    InterpolatedForce::new(self.start * facing, self.end * facing, self.duration)
+  }
+}
+
+/// Where an attack can be performed from
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttackStance {
+  Grounded,
+  Airborne,
+  Both,
+}
+
+impl Default for AttackStance {
+  fn default() -> Self {
+    AttackStance::Grounded
+  }
+}
+
+impl AttackStance {
+  /// Can an attack with this stance be performed while grounded or airborne
+  pub fn allows(&self, airborne: bool) -> bool {
+    match self {
+      AttackStance::Grounded => !airborne,
+      AttackStance::Airborne => airborne,
+      AttackStance::Both => true,
+    }
+  }
+}
+
+/// An inclusive range of frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrameRange {
+  pub start: u8,
+  pub end: u8,
+}
+
+impl FrameRange {
+  pub fn contains(&self, frame: u8) -> bool {
+    frame >= self.start && frame <= self.end
+  }
+}
+
+/// What an attack can be cancelled into
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CancelTarget {
+  /// A specific attack, by name
+  Attack(String),
+  /// Any attack of the given category
+  Category(AttackCategory),
+  Jump,
+  SuperJump,
+  Dash,
+}
+
+/// A route out of an attack, and the conditions it can be taken under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelRoute {
+  pub target: CancelTarget,
+  /// Can the route be taken after the attack hits
+  #[serde(default)]
+  pub on_hit: bool,
+  /// Can the route be taken after the attack is blocked
+  #[serde(default)]
+  pub on_block: bool,
+  /// Can the route be taken when the attack hasn't connected
+  #[serde(default)]
+  pub on_whiff: bool,
+  /// Frames of the attack the route is open on
+  pub window: FrameRange,
+}
+
+impl CancelRoute {
+  /// Is the route open on this frame, given what the attack has connected with
+  pub fn is_open(&self, frame: u8, hit_state: HitState) -> bool {
+    let connected = match hit_state {
+      HitState::Hit => self.on_hit,
+      HitState::Blocked => self.on_block,
+      HitState::None => self.on_whiff,
+    };
+    connected && self.window.contains(frame)
+  }
+
+  /// Can this route cancel into the passed attack
+  pub fn allows(&self, attack: &Attack) -> bool {
+    match &self.target {
+      CancelTarget::Attack(name) => *name == attack.name,
+      CancelTarget::Category(category) => *category == attack.category,
+      _ => false,
+    }
+  }
+}
+
+/// Grab performed by throw attacks, throwboxes ignore how the opponent is blocking
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Throw {
+  /// Damage dealt when the opponent is released
+  pub damage: u8,
+  /// where should the throwbox be placed in relation to the player
+  pub position: Vec2,
+  /// size of the throwbox
+  pub size: Vec2,
+  /// what frame of the attack should the throwbox be generated
+  pub frame: u8,
+  /// how many frames the throwbox stays out
+  pub active: u8,
+  /// how long the throw lasts once the opponent has been grabbed
+  pub duration: u8,
+  /// what frame of the throw the opponent is released
+  pub release: u8,
+  /// how many frames the opponent has to break the throw
+  pub tech_window: u8,
+  /// Force applied to the opponent when they are released
+  pub force: Vec2,
+  /// Does the throw grab airborne opponents instead of grounded ones
+  pub air: bool,
+}
+
+impl Throw {
+  /// Create a throw from it's serialized counterpart
+  pub fn from_serialized(s: ThrowSerialized) -> Self {
+    Throw {
+      damage: s.damage,
+      position: s.position.to_vec2(),
+      size: s.size.to_vec2(),
+      frame: s.frame,
+      active: s.active,
+      duration: s.duration,
+      release: s.release,
+      tech_window: s.tech_window,
+      force: s.force.to_vec2(),
+      air: s.air,
     }
   }
 }
@@ -41,4 +386,67 @@ pub struct AttackSerialized {
   pub name: String,
   pub hitbox_events: Vec<HitboxEventSerialized>,
   pub busy: u8,
+  #[serde(default)]
+  pub throw: Option<ThrowSerialized>,
+  #[serde(default)]
+  pub command: Option<CommandType>,
+  #[serde(default)]
+  pub category: Option<AttackCategory>,
+  #[serde(default)]
+  pub stance: AttackStance,
+  #[serde(default)]
+  pub landing_recovery: Option<u8>,
+  #[serde(default)]
+  pub cancels: Vec<CancelRoute>,
+  #[serde(default)]
+  pub movement_events: Vec<AttackMovementSerialized>,
+  #[serde(default)]
+  pub strike_invulnerable: Option<FrameRange>,
+  #[serde(default)]
+  pub throw_invulnerable: Option<FrameRange>,
+  #[serde(default)]
+  pub projectile_invulnerable: Option<FrameRange>,
+  #[serde(default)]
+  pub armor: Option<Armor>,
+  #[serde(default)]
+  pub counter_hit: Option<FrameRange>,
+  #[serde(default)]
+  pub punish_counter: Option<FrameRange>,
+  #[serde(default)]
+  pub meter_cost: u16,
+  #[serde(default)]
+  pub superflash: Option<u8>,
+}
+
+/// Serialized version of an attack movement
+#[derive(Deserialize, Serialize)]
+pub struct AttackMovementSerialized {
+  pub frame: u8,
+  pub start: Vec2Serialzed,
+  #[serde(default)]
+  pub end: Vec2Serialzed,
+  pub duration: u8,
+  #[serde(default)]
+  pub reset_velocity: bool,
+}
+
+/// Serialized version of a throw
+#[derive(Deserialize, Serialize)]
+pub struct ThrowSerialized {
+  pub damage: u8,
+  pub position: Vec2Serialzed,
+  pub size: Vec2Serialzed,
+  pub frame: u8,
+  #[serde(default = "default_throw_active")]
+  pub active: u8,
+  pub duration: u8,
+  pub release: u8,
+  pub tech_window: u8,
+  pub force: Vec2Serialzed,
+  #[serde(default)]
+  pub air: bool,
+}
+
+fn default_throw_active() -> u8 {
+  2
 }
@@ -1,46 +1,202 @@
 use bevy::prelude::*;
 use crate::{
+  animation::AnimationController,
   character::{
     CharacterMovement,
     CharacterState,
     PlayerId
   },
+  character_library::CharacterLibrary,
   collision::{
+    boxes_overlap,
+    Collision,
+    CollisionEvent,
     Hitbox,
-    SpawnHitbox
-  }
+    HitboxOwner,
+    Projectile,
+    SpawnHitbox,
+    Throwbox
+  },
+  constants::PROJECTILE_LIMIT,
+  stage::StageBounds,
+  utils::countdown
 };
 
 
 #[doc(hidden)]
 pub fn spawn_hitboxes(
   mut coms: Commands,
-  query: Query<(&PlayerId, &CharacterState, &CharacterMovement, &Transform)>,
+  library: Res<CharacterLibrary>,
+  query: Query<(Entity, &PlayerId, &Name, &CharacterState, &CharacterMovement, &Transform, &Handle<TextureAtlas>)>,
+  projectile_query: Query<&PlayerId, With<Projectile>>,
 ) {
-  for (player_id, state, movement, transform) in query.iter() {
+  for (entity, player_id, name, state, movement, transform, texture_atlas) in query.iter() {
     if let Some(hitbox_events) = state.get_hitbox_events_this_frame() {
+      let mut projectiles_on_screen = projectile_query.iter().filter(|p| *p == player_id).count();
       for he in hitbox_events.iter() {
+        if let Some(data) = &he.projectile {
+          if projectiles_on_screen >= PROJECTILE_LIMIT {
+            continue;
+          }
+          projectiles_on_screen += 1;
+          let animation_controller = data.animation.as_ref().map(|animation| {
+            let mut controller = AnimationController::new(name.as_str(), &library);
+            controller.play(animation.clone());
+            controller
+          });
+          coms.spawn_projectile(
+            player_id,
+            he,
+            data,
+            transform,
+            movement.facing_vector,
+            texture_atlas.clone(),
+            animation_controller
+          );
+          continue;
+        }
         coms.spawn_hitbox(
           player_id,
+          entity,
           he,
           transform,
           movement.facing_vector
         )
       }
     }
+
+    if let Some(throw) = state.get_throw_this_frame() {
+      coms.spawn_throwbox(
+        player_id,
+        entity,
+        &throw,
+        transform,
+        movement.facing_vector
+      )
+    }
   }
 }
 
 #[doc(hidden)]
 pub fn manage_hitboxes(
   mut coms: Commands,
-  mut query: Query<(&mut Hitbox, Entity)>,
+  mut query: Query<(&mut Hitbox, Option<&mut Projectile>, Entity)>,
+  mut throwbox_query: Query<(&mut Throwbox, Entity)>,
 ) {
-  for (mut hitbox, entity) in query.iter_mut() {
+  for (mut hitbox, projectile, entity) in query.iter_mut() {
     if hitbox.is_finished() {
-      coms.entity(entity).despawn();
+      // Projectiles are only ever despawned by manage_projectiles
+      match projectile {
+        Some(mut projectile) => projectile.destroy(),
+        None => coms.entity(entity).despawn_recursive(),
+      }
     } else {
       hitbox.tick();
     }
   }
-} 
+
+  for (mut throwbox, entity) in throwbox_query.iter_mut() {
+    if throwbox.is_finished() {
+      coms.entity(entity).despawn_recursive();
+    } else {
+      throwbox.tick();
+    }
+  }
+}
+
+#[doc(hidden)]
+pub fn release_throws(
+  query: Query<(&PlayerId, &CharacterState)>,
+  mut collision_writer: EventWriter<CollisionEvent>,
+) {
+  for (player_id, state) in query.iter() {
+    if let Some(throw) = state.get_throw_release_this_frame() {
+      collision_writer.send(
+        CollisionEvent {
+          collision: Collision::new(Hitbox::from_throw(&throw), false),
+          player_id: *player_id,
+          recieving_player_id: player_id.opponent(),
+        }
+      );
+    }
+  }
+}
+
+#[doc(hidden)]
+pub fn move_projectiles(
+  bounds: Res<StageBounds>,
+  mut query: Query<(&mut Projectile, &mut Transform)>,
+) {
+  for (mut projectile, mut transform) in query.iter_mut() {
+    transform.translation += Vec3::new(projectile.velocity.x, projectile.velocity.y, 0.0);
+    if bounds.is_outside(transform.translation.x) || transform.translation.y < 0.0 {
+      projectile.destroy();
+    }
+  }
+}
+
+/// Spend a projectile's hits as it connects, then despawn every projectile that ran out of hits,
+/// expired, left the stage or was clashed out this frame
+#[doc(hidden)]
+pub fn manage_projectiles(
+  mut coms: Commands,
+  mut query: Query<(Entity, &mut Projectile, &mut Hitbox)>,
+) {
+  for (entity, mut projectile, mut hitbox) in query.iter_mut() {
+    projectile.cooldown = countdown(projectile.cooldown);
+    if !hitbox.can_hit() {
+      hitbox.reset_hit_state();
+      projectile.spend_hit();
+    }
+    if projectile.is_spent() {
+      coms.entity(entity).despawn_recursive();
+    }
+  }
+}
+
+/// Opposing projectiles that overlap trade hits with each other
+#[doc(hidden)]
+pub fn clash_projectiles(
+  mut query: Query<(Entity, &PlayerId, &mut Projectile, &Hitbox, &Transform)>,
+) {
+  let projectiles: Vec<(Entity, PlayerId, Vec2, Vec2)> = query
+    .iter_mut()
+    .filter(|(_, _, projectile, _, _)| projectile.cooldown == 0 && !projectile.is_spent())
+    .map(|(entity, player_id, _, hitbox, transform)| (entity, *player_id, transform.translation.truncate(), hitbox.get_size()))
+    .collect();
+
+  let mut clashed: Vec<Entity> = Vec::new();
+  for (i, a) in projectiles.iter().enumerate() {
+    for b in projectiles.iter().skip(i + 1) {
+      if a.1 == b.1 || clashed.contains(&a.0) || clashed.contains(&b.0) {
+        continue;
+      }
+      if boxes_overlap(a.2, a.3, b.2, b.3) {
+        clashed.push(a.0);
+        clashed.push(b.0);
+      }
+    }
+  }
+
+  for entity in clashed {
+    if let Ok((_, _, mut projectile, _, _)) = query.get_mut(entity) {
+      projectile.spend_hit();
+    }
+  }
+}
+
+/// Remove the hitboxes and throwboxes of characters who were hit or thrown out of their attack
+#[doc(hidden)]
+pub fn remove_interrupted_hitboxes(
+  mut coms: Commands,
+  box_query: Query<(Entity, &HitboxOwner)>,
+  owner_query: Query<&CharacterState>,
+) {
+  for (entity, owner) in box_query.iter() {
+    if let Ok(state) = owner_query.get(owner.entity) {
+      if state.is_interrupted() {
+        coms.entity(entity).despawn_recursive();
+      }
+    }
+  }
+}
@@ -1,6 +1,32 @@
 use bevy::prelude::*;
 use lerp::Lerp;
-use crate::inputs::PlayerData;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+use crate::{
+  collision::CollisionEvent,
+  constants::{
+    CAMERA_FOLLOW_SPEED,
+    HEAVY_HIT_LEVEL,
+    HEAVY_HIT_SHAKE,
+    KO_SHAKE,
+    SUPERFLASH_ZOOM
+  },
+  inputs::PlayerData,
+  meter::SuperflashEvent,
+  round::{
+    RoundPhase,
+    RoundPhaseEvent
+  },
+  stage::StageBounds
+};
+
+/// How far the camera zooms in and out based on the distance between players
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CameraZoom {
+  pub max_scale: f32,
+  pub min_scale: f32,
+  pub max_distance: f32,
+}
 
 /// Moves and zooms the camera based on player positions
 #[derive(Component)]
@@ -10,6 +36,12 @@ pub struct CameraController {
   pub current_scale: f32,
   pub max_distance: f32,
   pub y_padding: f32,
+  /// How high players can get before the camera starts following them up
+  pub y_deadzone: f32,
+  /// Scale the camera eases towards `current_scale` with, before effects are applied
+  base_scale: f32,
+  /// Effects currently playing, and how many frames they've been playing for
+  effects: Vec<(CameraEffect, u16)>,
 }
 
 impl CameraController {
@@ -20,7 +52,7 @@ impl CameraController {
     } else {
       distance/self.max_distance
     };
-    
+
     if scale > self.max_scale {
       self.current_scale = self.max_scale;
     }
@@ -33,6 +65,184 @@ impl CameraController {
       self.current_scale = scale;
     }
   }
+
+  /// Ease the base scale towards the scale set from the distance between players
+  #[allow(unstable_name_collisions)]
+  pub fn follow_scale(&mut self, weight: f32) {
+    self.base_scale = self.base_scale.lerp(self.current_scale, weight);
+  }
+
+  pub fn get_base_scale(&self) -> f32 {
+    return self.base_scale;
+  }
+
+  /// Returns the position and scale the camera shows this frame, blending the position it follows
+  /// and the base scale with the playing effects. Effects never change the base, so they leave no trace once they end
+  #[allow(unstable_name_collisions)]
+  pub fn get_view(&self, follow_position: Vec2) -> (Vec2, f32) {
+    let (mut position, mut scale) = (follow_position, self.base_scale);
+    if let Some((point, effect_scale, weight)) = self.get_framing() {
+      position = position.lerp(point, weight);
+      scale = scale.lerp(effect_scale, weight);
+    }
+    return (position + self.get_shake_offset(), scale);
+  }
+
+  pub fn set_zoom(&mut self, zoom: CameraZoom) {
+    self.max_scale = zoom.max_scale;
+    self.min_scale = zoom.min_scale;
+    self.max_distance = zoom.max_distance;
+  }
+
+  /// Returns where the camera should be centered, given the point between the players and half the width of the view,
+  /// without showing anything past the edges of the stage. The stage is centered between it's walls
+  pub fn get_target_position(&self, mid_point: Vec2, half_view_width: f32, bounds: &StageBounds) -> Vec2 {
+    let center = (bounds.left_wall + bounds.right_wall) / 2.0;
+    let edge = (bounds.width / 2.0 - half_view_width).max(0.0);
+    let x = mid_point.x.clamp(center - edge, center + edge);
+    let y = (mid_point.y - self.y_deadzone).max(0.0).min(bounds.camera_max_y);
+    return Vec2::new(x, y + self.y_padding);
+  }
+
+  /// Shake the camera, the shake gets weaker by `decay` every frame
+  pub fn shake(&mut self, amplitude: f32, frequency: f32, decay: f32, duration: u16) {
+    self.play(CameraEffect::Shake {amplitude, frequency, decay, duration});
+  }
+
+  /// Zoom in on a point for a moment, easing back out over the last frames of the effect
+  pub fn punch_in(&mut self, point: Vec2, scale: f32, duration: u16) {
+    self.play(CameraEffect::PunchIn {point, scale, duration});
+  }
+
+  /// Pan and zoom through a series of keyframes
+  pub fn play_sequence(&mut self, keyframes: Vec<CameraKeyframe>) {
+    self.play(CameraEffect::Sequence {keyframes});
+  }
+
+  pub fn play(&mut self, effect: CameraEffect) {
+    self.effects.push((effect, 0));
+  }
+
+  pub fn stop_effects(&mut self) {
+    self.effects.clear();
+  }
+
+  pub fn has_effects(&self) -> bool {
+    return !self.effects.is_empty();
+  }
+
+  /// Advance every effect by a frame, removing the ones that finished
+  pub fn tick_effects(&mut self) {
+    for (_, frame) in self.effects.iter_mut() {
+      *frame += 1;
+    }
+    self.effects.retain(|(effect, frame)| *frame < effect.get_duration());
+  }
+
+  /// Returns the combined offset of every shake playing
+  pub fn get_shake_offset(&self) -> Vec2 {
+    self.effects
+      .iter()
+      .map(|(effect, frame)| effect.get_shake_offset(*frame))
+      .sum()
+  }
+
+  /// Returns the position and scale the camera is overridden to by the latest punch in or sequence, if any
+  pub fn get_framing(&self) -> Option<(Vec2, f32, f32)> {
+    self.effects
+      .iter()
+      .rev()
+      .find_map(|(effect, frame)| effect.get_framing(*frame))
+  }
+}
+
+/// A single point in a camera sequence
+#[derive(Debug, Clone, Copy)]
+pub struct CameraKeyframe {
+  /// Point the camera centers on
+  pub position: Vec2,
+  pub scale: f32,
+  /// Frames spent moving from the previous keyframe to this one
+  pub frames: u16,
+}
+
+/// Effects the camera can play on top of following the players
+#[derive(Debug, Clone)]
+pub enum CameraEffect {
+  Shake {
+    /// Distance the camera moves on the first frame of the shake
+    amplitude: f32,
+    /// Shakes per second
+    frequency: f32,
+    /// Portion of the amplitude kept every frame
+    decay: f32,
+    duration: u16,
+  },
+  PunchIn {
+    point: Vec2,
+    scale: f32,
+    duration: u16,
+  },
+  Sequence {
+    keyframes: Vec<CameraKeyframe>,
+  },
+}
+
+impl CameraEffect {
+  pub fn get_duration(&self) -> u16 {
+    match self {
+      CameraEffect::Shake {duration, ..} | CameraEffect::PunchIn {duration, ..} => *duration,
+      CameraEffect::Sequence {keyframes} => keyframes.iter().map(|k| k.frames).sum(),
+    }
+  }
+
+  /// Offset of a shake on the given frame, the same frame always shakes the same way
+  pub fn get_shake_offset(&self, frame: u16) -> Vec2 {
+    match self {
+      CameraEffect::Shake {amplitude, frequency, decay, duration:_} => {
+        let strength = amplitude * decay.powi(frame as i32);
+        let phase = frame as f32 * frequency * TAU / 60.0;
+        Vec2::new(phase.sin(), (phase * 1.5).cos()) * strength
+      },
+      _ => Vec2::ZERO
+    }
+  }
+
+  /// Returns the point the effect centers the camera on, it's scale, and how much it overrides the normal camera from 0 to 1
+  #[allow(unstable_name_collisions)]
+  pub fn get_framing(&self, frame: u16) -> Option<(Vec2, f32, f32)> {
+    match self {
+      CameraEffect::PunchIn {point, scale, duration} => {
+        // Snap in, then ease back out over the last third of the effect
+        let release = (duration / 3).max(1);
+        let remaining = duration.saturating_sub(frame);
+        let weight = (remaining as f32 / release as f32).min(1.0);
+        Some((*point, *scale, weight))
+      },
+      CameraEffect::Sequence {keyframes} => {
+        let mut start = frame;
+        let mut previous: Option<&CameraKeyframe> = None;
+        for keyframe in keyframes.iter() {
+          if start < keyframe.frames {
+            let t = start as f32 / keyframe.frames as f32;
+            return match previous {
+              Some(p) => Some((p.position.lerp(keyframe.position, t), p.scale.lerp(keyframe.scale, t), 1.0)),
+              None => Some((keyframe.position, keyframe.scale, 1.0))
+            };
+          }
+          start -= keyframe.frames;
+          previous = Some(keyframe);
+        }
+        previous.map(|k| (k.position, k.scale, 1.0))
+      },
+      _ => None
+    }
+  }
+}
+
+/// Sent to play an effect on every camera with a [CameraController]
+pub struct CameraEffectEvent {
+  pub effect: CameraEffect,
 }
 
 impl Default for CameraController {
@@ -42,25 +252,82 @@ impl Default for CameraController {
       min_scale: 0.5,
       current_scale: 0.5,
       max_distance: 650.0,
-      y_padding: 50.0
+      y_padding: 50.0,
+      y_deadzone: 60.0,
+      base_scale: 0.5,
+      effects: Vec::new()
     }
   }
 }
 
+/// Place the camera where it's controller frames the players this frame
 #[doc(hidden)]
-#[allow(unstable_name_collisions)]
 pub fn set_camera_scale(
-  mut query: Query<(&mut OrthographicProjection, &mut CameraController, &mut Transform)>,
-  player_data: Res<PlayerData>
+  mut query: Query<(&mut OrthographicProjection, &CameraController, &mut Transform)>,
+  player_data: Res<PlayerData>,
+  bounds: Res<StageBounds>,
 ) {
-  for (mut proj, mut controller, mut transform) in query.iter_mut() {
+  for (mut proj, controller, mut transform) in query.iter_mut() {
+    let half_view_width = (proj.right - proj.left) * controller.get_base_scale() / 2.0;
+    let follow_position = controller.get_target_position(player_data.get_mid_point(), half_view_width, &bounds);
+    let (position, scale) = controller.get_view(follow_position);
+
+    proj.scale = scale;
+    transform.translation = Vec3::new(position.x, position.y, transform.translation.z);
+  }
+}
+
+/// Start effects sent by other systems, and advance the zoom and the effects already playing once per frame
+#[doc(hidden)]
+pub fn manage_camera_effects(
+  mut effect_reader: EventReader<CameraEffectEvent>,
+  player_data: Res<PlayerData>,
+  bounds: Res<StageBounds>,
+  mut query: Query<&mut CameraController>,
+) {
+  let effects: Vec<CameraEffect> = effect_reader.iter().map(|e| e.effect.clone()).collect();
+  for mut controller in query.iter_mut() {
+    if let Some(zoom) = bounds.zoom {
+      controller.set_zoom(zoom);
+    }
     controller.set_scale_from_distance(player_data.get_distance());
-    let lerped_scale = proj.scale.lerp(controller.current_scale, 0.05);
-    proj.scale = lerped_scale;
-    
-    let mid_point = player_data.get_mid_point();
+    controller.follow_scale(CAMERA_FOLLOW_SPEED);
+    controller.tick_effects();
+    for effect in effects.iter() {
+      controller.play(effect.clone());
+    }
+  }
+}
 
-    let new_pos = Vec3::new(mid_point.x, mid_point.y + controller.y_padding, transform.translation.z);
-    transform.translation = new_pos;
+/// Play camera effects for heavy hits, supers and KOs
+#[doc(hidden)]
+pub fn trigger_camera_effects(
+  mut collision_reader: EventReader<CollisionEvent>,
+  mut superflash_reader: EventReader<SuperflashEvent>,
+  mut phase_reader: EventReader<RoundPhaseEvent>,
+  player_data: Res<PlayerData>,
+  mut effect_writer: EventWriter<CameraEffectEvent>,
+) {
+  for event in collision_reader.iter() {
+    if !event.collision.blocked && event.collision.hitbox.get_attack_level() >= HEAVY_HIT_LEVEL {
+      effect_writer.send(CameraEffectEvent {effect: HEAVY_HIT_SHAKE});
+    }
+  }
+
+  for event in superflash_reader.iter() {
+    let point = player_data.positions
+      .iter()
+      .find(|p| p.player_id == event.player_id)
+      .map(|p| p.get_position().truncate())
+      .unwrap_or(Vec2::ZERO);
+    effect_writer.send(CameraEffectEvent {
+      effect: CameraEffect::PunchIn {point, scale: SUPERFLASH_ZOOM, duration: event.duration as u16}
+    });
+  }
+
+  for event in phase_reader.iter() {
+    if event.phase == RoundPhase::KO {
+      effect_writer.send(CameraEffectEvent {effect: KO_SHAKE});
+    }
   }
 }
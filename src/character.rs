@@ -1,28 +1,62 @@
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::Inspectable;
-use bevy::{
-  diagnostic::{
-    Diagnostics,
-    FrameTimeDiagnosticsPlugin
-  },
-  prelude::*
-};
+use bevy::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use crate::{
   character_library::CharacterLibrary,
-  attacks::Attack,
-  collision::HitboxEvent,
+  attacks::{
+    Attack,
+    AttackMovement,
+    CancelTarget,
+    Throw
+  },
+  collision::{
+    HitboxEvent,
+    HitState,
+    Hurtbox,
+    BlockState,
+    Collision,
+    CollisionEvent,
+    CounterHit,
+    CounterHitEvent,
+    HitGroups,
+    Pushbox,
+    ThrowEvent,
+    push_apart
+  },
+  constants::{
+    JUGGLE_GRAVITY_SCALING,
+    BOUNCE_RESTITUTION,
+    THROW_TECH_DURATION,
+    THROW_TECH_PUSHBACK,
+    MIN_BACKLEAP_AIRTIME,
+    DEFAULT_HEALTH
+  },
   inputs::{
     FighterInputBuffer,
     PlayerData,
     CommandType
   },
-  utils::countdown,
+  utils::{
+    countdown,
+    MotionGroups
+  },
   physics::{
     InterpolatedForce,
     CustomLerp
   },
+  combo::{
+    ComboTracker,
+    Health
+  },
+  round::RoundConfig,
+  stage::StageBounds,
+  meter::{
+    SuperMeter,
+    Superflash,
+    SuperflashEvent
+  },
   animation::{
     AnimationTransitionEvent,
     AnimationTransition, AnimationController
@@ -42,7 +76,9 @@ pub enum CharacterState {
     ///The current attack being executed 
     attack: Attack,
     ///Can the current attack me cancelled prematurely 
-    cancellable: bool
+    cancellable: bool,
+    ///Has the current attack hit or been blocked
+    hit_state: HitState
   },
   AttackingAirborne {
     ///The number of frames until the action completes naturally
@@ -50,14 +86,18 @@ pub enum CharacterState {
     ///The current attack being executed 
     attack: Attack,
     ///Can the current attack me cancelled prematurely 
-    cancellable: bool
+    cancellable: bool,
+    ///Has the current attack hit or been blocked
+    hit_state: HitState
   },
   Crouching,
   Jumpsquat {
     ///The number of frames until the action completes naturally
     duration: u8,
     ///The Velocity of the buffered jump 
-    velocity: Vec2 
+    velocity: Vec2,
+    ///Is the buffered jump a super jump
+    superjump: bool
   },
   AirJumpsquat {
     ///The number of frames until the action completes naturally
@@ -70,12 +110,95 @@ pub enum CharacterState {
     busy: u8
   },
   Falling,
-  Juggle,
+  Hitstun {
+    ///The number of frames until the character can act
+    duration: u8,
+    ///The number of hits taken since the character could last act
+    hits: u8
+  },
+  Blockstun {
+    ///The number of frames until the character can act
+    duration: u8
+  },
+  AirBlockstun {
+    ///The number of frames until the character can act
+    duration: u8
+  },
+  Juggle {
+    ///The number of frames of hitstun remaining
+    duration: u8,
+    ///The number of hits taken since the character could last act
+    hits: u8,
+    ///Will the character bounce off of the next wall they reach
+    wall_bounce: bool,
+    ///Will the character bounce off of the ground the next time they land
+    ground_bounce: bool,
+    ///Will the character be put into a hard knockdown when they land
+    hard_knockdown: bool
+  },
+  AirTech {
+    ///The number of frames until the character can act
+    duration: u8
+  },
+  SoftKnockdown {
+    ///The number of frames until the character starts getting up
+    duration: u8
+  },
+  HardKnockdown {
+    ///The number of frames until the character starts getting up
+    duration: u8,
+    ///Has the character already delayed their wakeup
+    delayed: bool
+  },
+  Wakeup {
+    ///The number of frames until the character can act
+    duration: u8,
+    ///Did the character quick rise out of a soft knockdown
+    quick_rise: bool
+  },
+  Throwing {
+    ///The number of frames until the action completes naturally
+    duration: u8,
+    ///The throw being performed
+    throw: Throw
+  },
+  Thrown {
+    ///The number of frames until the character is released if the throw is interrupted
+    duration: u8,
+    ///The number of frames left to break the throw
+    tech: u8,
+    ///Was the character grabbed out of the air, so they're released into a juggle
+    airborne: bool
+  },
+  ThrowTech {
+    ///The number of frames until the character can act
+    duration: u8
+  },
   Dashing,
   BackDashing {
     ///The number of frames until the action completes naturally
     duration: u8
   },
+  BackTeleporting {
+    ///The number of frames until the action completes naturally
+    duration: u8,
+    ///The number of frames the character stays hidden while repositioning
+    vanish: u8,
+    ///The velocity applied each frame while hidden
+    velocity: Vec2
+  },
+  BackLeaping {
+    ///The number of frames the character is stuck in after landing
+    busy: u8
+  },
+  BackLeapLanding {
+    ///The number of frames until the action completes naturally
+    duration: u8
+  },
+  LandingRecovery {
+    ///The number of frames until the character can act after landing
+    duration: u8
+  },
   AirDashing {
     ///The number of frames until the player can act out of the state
     busy: u8,
@@ -110,11 +233,35 @@ impl CharacterState {
   fn tick(&mut self) {
     use CharacterState::*;
     match self {
-      Attacking {duration, attack: _, cancellable:_} => { *duration = countdown(*duration);},
-      Jumpsquat {duration, velocity:_ } => { *duration = countdown(*duration);},
+      Attacking {duration, attack, cancellable, hit_state}
+      | AttackingAirborne {duration, attack, cancellable, hit_state} => {
+        *duration = countdown(*duration);
+        *cancellable = !attack.open_cancels(attack.current_frame(*duration), *hit_state).is_empty();
+      },
+      Jumpsquat {duration, velocity:_, superjump:_ } => { *duration = countdown(*duration);},
       AirJumpsquat {duration, velocity: _ } => { *duration = countdown(*duration);},
       BackDashing {duration} => { *duration = countdown(*duration);},
+      BackTeleporting {duration, vanish, velocity:_} => {
+        *duration = countdown(*duration);
+        *vanish = countdown(*vanish);
+      },
+      BackLeapLanding {duration} => { *duration = countdown(*duration);},
+      LandingRecovery {duration} => { *duration = countdown(*duration);},
       Rising {busy} => {*busy = countdown(*busy)},
+      Hitstun {duration, hits:_} => { *duration = countdown(*duration);},
+      Blockstun {duration} => { *duration = countdown(*duration);},
+      AirBlockstun {duration} => { *duration = countdown(*duration);},
+      Juggle {duration, hits:_, wall_bounce:_, ground_bounce:_, hard_knockdown:_} => { *duration = countdown(*duration);},
+      AirTech {duration} => { *duration = countdown(*duration);},
+      SoftKnockdown {duration} => { *duration = countdown(*duration);},
+      HardKnockdown {duration, delayed:_} => { *duration = countdown(*duration);},
+      Wakeup {duration, quick_rise:_} => { *duration = countdown(*duration);},
+      Throwing {duration, throw:_} => { *duration = countdown(*duration);},
+      Thrown {duration, tech, airborne:_} => {
+        *duration = countdown(*duration);
+        *tech = countdown(*tech);
+      },
+      ThrowTech {duration} => { *duration = countdown(*duration);},
       AirDashing {busy,duration, velocity:_} => {
         *busy = countdown(*busy); 
         *duration = countdown(*duration);
@@ -127,21 +274,29 @@ impl CharacterState {
     }
   } 
   /// updates a character state, advancing it's timers and changing it based on input and character movement
-  pub fn update(&mut self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3) -> Option<AnimationTransition> {
+  pub fn update(&mut self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3, bounds: &StageBounds) -> Option<AnimationTransition> {
     use CharacterState::*;
     self.tick();
     
     let new_state = match self {
       Idle | Walking | BackWalking | Crouching => self.from_neutral_states(buffer, movement, attacks, name, library),
       Dashing => self.from_dashing(buffer, movement),
-      Jumpsquat { duration:_,velocity:_ } => self.from_jump_squat(movement),
+      Jumpsquat { duration:_,velocity:_, superjump:_ } | AirJumpsquat { duration:_, velocity:_ } => self.from_jump_squat(movement),
       Rising { busy: _ } | Falling => self.from_neutral_airborne(buffer, movement, attacks, name, library, position),
-      BackDashing { duration:_ } => self.from_backdashing(buffer, movement, attacks, name, library,),
-      Attacking {duration:_, attack:_, cancellable:_} => self.from_attacking(buffer, movement, attacks, name, library),
-      AirDashing { busy:_,duration:_,velocity:_} | AirBackDashing { busy:_,duration:_,velocity:_} => self.from_air_dashing(buffer, movement, attacks, name, library),
+      BackDashing { duration:_ } | BackTeleporting { duration:_, vanish:_, velocity:_ } | BackLeapLanding { duration:_ } => self.from_backdashing(buffer, movement, attacks, name, library,),
+      BackLeaping { busy:_ } => self.from_backleaping(movement, position),
+      LandingRecovery { duration:_ } => self.from_landing_recovery(buffer, movement, attacks, name, library),
+      Hitstun { duration:_, hits:_ } | Blockstun { duration:_ } | AirBlockstun { duration:_ } => self.from_stun(buffer, movement, attacks, name, library, position),
+      Juggle { duration:_, hits:_, wall_bounce:_, ground_bounce:_, hard_knockdown:_ } => self.from_juggle(buffer, movement, position, bounds),
+      AirTech { duration:_ } => self.from_air_tech(buffer, movement, attacks, name, library, position),
+      SoftKnockdown { duration:_ } | HardKnockdown { duration:_, delayed:_ } | Wakeup { duration:_, quick_rise:_ } => self.from_knockdown(buffer, movement, attacks, name, library),
+      Throwing { duration:_, throw:_ } | Thrown { duration:_, tech:_, airborne:_ } | ThrowTech { duration:_ } => self.from_throw_states(buffer, movement, attacks, name, library, position),
+      Attacking {duration:_, attack:_, cancellable:_, hit_state:_}
+      | AttackingAirborne {duration:_, attack:_, cancellable:_, hit_state:_} => self.from_attacking(buffer, movement, attacks, name, library, position),
+      AirDashing { busy:_,duration:_,velocity:_} | AirBackDashing { busy:_,duration:_,velocity:_} => self.from_air_dashing(buffer, movement, attacks, name, library, position),
       _ => self.clone()
     };
-    let transition = if self.clone() != new_state {
+    let transition = if self.clone() != new_state || new_state.is_attack_startup() {
       self.calculate_transition(&new_state)
     } else {
       None
@@ -155,9 +310,9 @@ impl CharacterState {
   ///  - Walking
   ///  - Backwalking
   ///  - Crouching
-  pub fn from_neutral_states(&self, buffer: &FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary)  -> Self {
+  pub fn from_neutral_states(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary)  -> Self {
     use CharacterState::*;
-    if let Some(attack) = attacks.attack_to_execute(buffer, name, library, true) {
+    if let Some(attack) = attacks.attack_to_execute(buffer, name, library, false) {
       return self.buffer_attack(attack);
     }
 
@@ -165,6 +320,12 @@ impl CharacterState {
       match ct {
         CommandType::DASH => return Dashing,
         CommandType::BACK_DASH => return self.buffer_backdash(movement),
+        CommandType::SUPER_JUMP => {
+          if buffer.current_motion.y_positive() {
+            buffer.consume_motion();
+            return Self::buffer_jump(buffer.current_motion, movement, true);
+          }
+        },
       _ => ()
       }               
     }
@@ -178,11 +339,22 @@ impl CharacterState {
     }
   }
 
-  /// Returns a new state based on the current state when in jump squat
+  /// Returns a new state based on the current state when in jump squat or air jump squat
   pub fn from_jump_squat(&self, movement: &mut CharacterMovement) -> Self{
     use CharacterState::*;
     match self {
-      Jumpsquat { duration, velocity } => {
+      Jumpsquat { duration, velocity, superjump } => {
+        if *duration == 0 {
+          movement.velocity = *velocity;
+          if *superjump {
+            movement.grant_superjump_air_options();
+          }
+          return Rising {busy: movement.jump_lockout};
+        } else {
+          return self.clone();
+        }
+      },
+      AirJumpsquat { duration, velocity } => {
         if *duration == 0 {
           movement.velocity = *velocity;
           return Rising {busy: movement.jump_lockout};
@@ -195,8 +367,15 @@ impl CharacterState {
   }
 
   /// Returns a new state based on input from dashing
-  pub fn from_dashing(&self, buffer: &FighterInputBuffer, movement: &CharacterMovement) -> Self {
+  pub fn from_dashing(&self, buffer: &mut FighterInputBuffer, movement: &CharacterMovement) -> Self {
     use CharacterState::*;
+    if let Some(CommandType::SUPER_JUMP) = buffer.command_type {
+      if buffer.current_motion.y_positive() {
+        buffer.consume_motion();
+        return Self::buffer_dash_jump(buffer.current_motion, movement, true);
+      }
+    }
+
     match buffer.current_motion {
       4 => return BackWalking,
       6 => return Dashing,
@@ -206,18 +385,18 @@ impl CharacterState {
     }
   }
 
-  pub fn from_air_dashing(&self, buffer: &FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary) -> Self {
+  pub fn from_air_dashing(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3) -> Self {
     use CharacterState::*;
     match self {
       AirDashing {busy:_ ,duration, velocity:_} => {
         if *duration == 0 {
-          return self.from_neutral_airborne(buffer, movement, attacks, name, library, Vec3::ONE);
+          return self.from_neutral_airborne(buffer, movement, attacks, name, library, position);
         }
         return self.clone();
       },
       AirBackDashing {busy:_,duration, velocity:_} => {
         if *duration == 0 {
-          return self.from_neutral_airborne(buffer, movement, attacks, name, library, Vec3::ONE);
+          return self.from_neutral_airborne(buffer, movement, attacks, name, library, position);
         }
         return self.clone();
       },
@@ -230,10 +409,10 @@ impl CharacterState {
   ///  - Falling
   ///  - Airdashing
   ///  - Airbackdashing
-  pub fn from_neutral_airborne(&self, buffer: &FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3) -> Self {
+  pub fn from_neutral_airborne(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3) -> Self {
     use CharacterState::*;
     if position.y <= 0.0 {
-      return Idle;
+      return self.landing_state(movement);
     }
     match self {
       Rising { busy } => {
@@ -250,11 +429,139 @@ impl CharacterState {
     };
   }
 
-  /// Returns a new state based on input and the backdash timer from backdash
-  pub fn from_backdashing(&self, buffer: &FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary) -> Self {
+  /// Returns a new state based on input and the backdash timer from the following states:
+  ///  - Backdashing
+  ///  - BackTeleporting
+  ///  - BackLeapLanding
+  pub fn from_backdashing(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary) -> Self {
+    use CharacterState::*;
+    match self {
+      BackDashing {duration} | BackTeleporting {duration, vanish:_, velocity:_} | BackLeapLanding {duration} => {
+        if *duration == 0 {
+          return self.from_neutral_states(buffer, movement, attacks, name, library);
+        }
+        return self.clone();
+      },
+      _ => return self.clone(),
+    }
+  }
+
+  /// Returns a new state from a back leap, landing into recovery once the character touches the ground
+  pub fn from_backleaping(&self, movement: &CharacterMovement, position: Vec3) -> Self {
+    use CharacterState::*;
+    match self {
+      BackLeaping {busy} => {
+        if position.y <= 0.0 && movement.is_falling() {
+          return BackLeapLanding {duration: *busy};
+        }
+        return self.clone();
+      },
+      _ => return self.clone(),
+    }
+  }
+
+  /// Returns a new state based on input and the stun timer from the following states:
+  ///  - Hitstun
+  ///  - Blockstun
+  ///  - AirBlockstun
+  pub fn from_stun(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3) -> Self {
+    use CharacterState::*;
+    match self {
+      Hitstun {duration, hits:_} | Blockstun {duration} => {
+        if *duration == 0 {
+          return self.from_neutral_states(buffer, movement, attacks, name, library);
+        }
+        return self.clone();
+      },
+      AirBlockstun {duration} => {
+        if position.y <= 0.0 {
+          return self.from_neutral_states(buffer, movement, attacks, name, library);
+        }
+        if *duration == 0 {
+          return Falling;
+        }
+        return self.clone();
+      },
+      _ => return self.clone(),
+    }
+  }
+
+  /// Returns a new state from juggle, bouncing off of walls and the ground, air teching once the untech time is over,
+  /// and knocking the character down on landing
+  pub fn from_juggle(&self, buffer: &FighterInputBuffer, movement: &mut CharacterMovement, position: Vec3, bounds: &StageBounds) -> Self {
+    use CharacterState::*;
+    match self {
+      Juggle {duration, hits, wall_bounce, ground_bounce, hard_knockdown} => {
+        let touching_wall = bounds.is_touching_wall(position.x, movement.velocity.x);
+        if *wall_bounce && touching_wall {
+          movement.velocity.x = -movement.velocity.x * BOUNCE_RESTITUTION;
+          return Juggle {duration: *duration, hits: *hits, wall_bounce: false, ground_bounce: *ground_bounce, hard_knockdown: *hard_knockdown};
+        }
+
+        if position.y <= 0.0 && movement.is_falling() {
+          if *ground_bounce {
+            movement.velocity.y = -movement.velocity.y * BOUNCE_RESTITUTION;
+            return Juggle {duration: *duration, hits: *hits, wall_bounce: *wall_bounce, ground_bounce: false, hard_knockdown: *hard_knockdown};
+          }
+          movement.velocity = Vec2::ZERO;
+          if *hard_knockdown {
+            return HardKnockdown {duration: movement.wakeup.hard_knockdown, delayed: false};
+          }
+          return SoftKnockdown {duration: movement.wakeup.soft_knockdown};
+        }
+
+        if *duration == 0 && buffer.current_press.any_pressed() {
+          return Self::buffer_air_tech(buffer.current_motion, movement);
+        }
+        return self.clone();
+      },
+      _ => return self.clone(),
+    }
+  }
+
+  /// Returns a new state from an air tech, falling once the tech is over
+  pub fn from_air_tech(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3) -> Self {
+    use CharacterState::*;
+    match self {
+      AirTech {duration} => {
+        if position.y <= 0.0 && movement.is_falling() {
+          return self.from_neutral_states(buffer, movement, attacks, name, library);
+        }
+        if *duration == 0 {
+          return Falling;
+        }
+        return self.clone();
+      },
+      _ => return self.clone(),
+    }
+  }
+
+  /// Returns a new state based on input and the wakeup timers from the following states:
+  ///  - SoftKnockdown
+  ///  - HardKnockdown
+  ///  - Wakeup
+  pub fn from_knockdown(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary) -> Self {
     use CharacterState::*;
     match self {
-      BackDashing {duration} => {
+      SoftKnockdown {duration} => {
+        if buffer.current_press.any_pressed() {
+          return Wakeup {duration: movement.wakeup.quick_rise, quick_rise: true};
+        }
+        if *duration == 0 {
+          return Wakeup {duration: movement.wakeup.wakeup, quick_rise: false};
+        }
+        return self.clone();
+      },
+      HardKnockdown {duration, delayed} => {
+        if !*delayed && buffer.current_motion.y_negative() {
+          return HardKnockdown {duration: duration.saturating_add(movement.wakeup.delayed_wakeup), delayed: true};
+        }
+        if *duration == 0 {
+          return Wakeup {duration: movement.wakeup.wakeup, quick_rise: false};
+        }
+        return self.clone();
+      },
+      Wakeup {duration, quick_rise:_} => {
         if *duration == 0 {
           return self.from_neutral_states(buffer, movement, attacks, name, library);
         }
@@ -264,25 +571,214 @@ impl CharacterState {
     }
   }
 
-  /// Returns a new state based on input and the attack timer from attack
-  pub fn from_attacking(&self, buffer: &FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary) -> Self {
+  /// Returns a new state based on input and the throw timers from the following states:
+  ///  - Throwing
+  ///  - Thrown
+  ///  - ThrowTech
+  pub fn from_throw_states(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3) -> Self {
     use CharacterState::*;
     match self {
-      Attacking {duration, attack:_, cancellable} => {
-        if *duration == 0 || *cancellable {
+      Thrown {duration, tech, airborne} => {
+        if *tech > 0 && attacks.has_throw_pressed(buffer, name, library, *airborne) {
+          return self.break_throw(movement);
+        }
+        if *duration == 0 {
+          return self.recover(buffer, movement, attacks, name, library, position);
+        }
+        return self.clone();
+      },
+      Throwing {duration, throw:_} | ThrowTech {duration} => {
+        if *duration == 0 {
+          return self.recover(buffer, movement, attacks, name, library, position);
+        }
+        return self.clone();
+      },
+      _ => return self.clone(),
+    }
+  }
+
+  /// Returns a ThrowTech state, pushing the character away from their opponent
+  pub fn break_throw(&self, movement: &mut CharacterMovement) -> Self {
+    movement.velocity = Vec2::ZERO;
+    movement.set_interpolated_force(
+      InterpolatedForce::new(
+        Vec2::new(-movement.facing_vector * THROW_TECH_PUSHBACK, 0.0),
+        Vec2::ZERO,
+        THROW_TECH_DURATION
+      )
+    );
+    return CharacterState::ThrowTech {duration: THROW_TECH_DURATION};
+  }
+
+  /// Returns a neutral state when grounded, or a falling state when airborne
+  fn recover(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3) -> Self {
+    if position.y > 0.0 {
+      return CharacterState::Falling;
+    }
+    return self.from_neutral_states(buffer, movement, attacks, name, library);
+  }
+
+  /// Returns an AirTech state, teching forward, backward or in place based on the direction held
+  fn buffer_air_tech(motion: u8, movement: &mut CharacterMovement) -> Self {
+    let direction = if motion.x_positive() {
+      movement.facing_vector
+    } else if motion.x_negative() {
+      -movement.facing_vector
+    } else {
+      0.0
+    };
+
+    movement.velocity = Vec2::new(direction * movement.walk_speed, movement.jump_height * 0.5);
+    return Self::AirTech {duration: movement.wakeup.air_tech};
+  }
+
+  /// Returns the state a character is put in after being struck, and applies the hit's force to their movement
+  pub fn take_hit(&self, collision: &Collision, movement: &mut CharacterMovement) -> Self {
+    use CharacterState::*;
+    let hitbox = &collision.hitbox;
+    let force = hitbox.get_force();
+    let away = -movement.facing_vector;
+    movement.interpolated_force = None;
+
+    if collision.blocked {
+      let duration = hitbox.get_blockstun();
+      if self.get_airborne() {
+        movement.velocity = Vec2::new(away * force.x, 0.0);
+        return AirBlockstun {duration};
+      }
+      movement.set_interpolated_force(
+        InterpolatedForce::new(Vec2::new(away * force.x, 0.0), Vec2::ZERO, duration)
+      );
+      return Blockstun {duration};
+    }
+
+    let hits = self.get_combo_hits();
+    let counter_bonus = collision.counter_hit.map_or(0, |c| c.get_hitstun_bonus());
+    let duration = hitbox.get_hitstun(hits).saturating_add(counter_bonus);
+    if hitbox.is_launcher() || self.get_airborne() {
+      movement.velocity = Vec2::new(away * force.x, force.y);
+      return Juggle {
+        duration,
+        hits: hits.saturating_add(1),
+        wall_bounce: hitbox.get_wall_bounce(),
+        ground_bounce: hitbox.get_ground_bounce(),
+        hard_knockdown: hitbox.get_hard_knockdown()
+      };
+    }
+
+    movement.set_interpolated_force(
+      InterpolatedForce::new(Vec2::new(away * force.x, 0.0), Vec2::ZERO, duration)
+    );
+    return Hitstun {duration, hits: hits.saturating_add(1)};
+  }
+
+  /// Returns a new state based on input and the attack timer from the following states:
+  ///  - Attacking
+  ///  - AttackingAirborne, which ends early if the character lands
+  pub fn from_attacking(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary, position: Vec3) -> Self {
+    use CharacterState::*;
+    match self {
+      AttackingAirborne {duration, attack, cancellable, hit_state} => {
+        if position.y <= 0.0 && movement.is_falling() {
+          return self.landing_state(movement);
+        }
+        if *duration == 0 {
+          return Falling;
+        }
+        if *cancellable {
+          if let Some(new_state) = self.buffer_cancel(attack, *duration, *hit_state, buffer, movement, attacks, name, library) {
+            return new_state;
+          }
+        }
+        return self.clone();
+      },
+      Attacking {duration, attack, cancellable, hit_state} => {
+        if *duration == 0 {
+          // Attacks that carry the character off the ground end in a fall
+          if position.y > 0.0 {
+            return Falling;
+          }
           return self.from_neutral_states(buffer, movement, attacks, name, library);
         }
+        if *cancellable {
+          if let Some(new_state) = self.buffer_cancel(attack, *duration, *hit_state, buffer, movement, attacks, name, library) {
+            return new_state;
+          }
+        }
         return self.clone();
       },
       _ => return self.clone(),
     }
   }
 
+  /// Returns a new state if the input matches one of the attack's open cancel routes
+  fn buffer_cancel(&self, attack: &Attack, duration: u8, hit_state: HitState, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary) -> Option<Self> {
+    let routes = attack.open_cancels(attack.current_frame(duration), hit_state);
+    let airborne = self.get_airborne();
+
+    if buffer.current_press.any_pressed() {
+      let cancel_into = attacks.find_attack_where(buffer, name, library, |next| {
+        next.stance.allows(airborne) && routes.iter().any(|route| route.allows(next))
+      });
+      if let Some(next) = cancel_into {
+        if airborne {
+          return Some(self.buffer_air_attack(next));
+        }
+        return Some(self.buffer_attack(next));
+      }
+    }
+
+    let has_route = |target: CancelTarget| routes.iter().any(|route| route.target == target);
+    let command = buffer.command_type;
+
+    if airborne {
+      if buffer.current_motion.y_positive() && has_route(CancelTarget::Jump) && movement.can_air_jump() {
+        movement.spend_air_jump();
+        return Some(Self::buffer_air_jump(buffer.current_motion, movement));
+      }
+      if let Some(CommandType::DASH) = command {
+        if has_route(CancelTarget::Dash) && movement.can_airdash() {
+          movement.spend_airdash();
+          return Some(self.buffer_airdash(movement, true));
+        }
+      }
+      return None;
+    }
+
+    if let Some(CommandType::SUPER_JUMP) = command {
+      if buffer.current_motion.y_positive() && has_route(CancelTarget::SuperJump) {
+        buffer.consume_motion();
+        return Some(Self::buffer_jump(buffer.current_motion, movement, true));
+      }
+    }
+
+    if buffer.current_motion.y_positive() && has_route(CancelTarget::Jump) {
+      return Some(Self::buffer_jump(buffer.current_motion, movement, false));
+    }
+
+    if let Some(CommandType::DASH) = command {
+      if has_route(CancelTarget::Dash) {
+        return Some(CharacterState::Dashing);
+      }
+    }
+    return None;
+  }
+
   // Returns a new state from input while aireborne
-  pub fn from_airborne_input(&self, buffer: &FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary) -> Self {
+  pub fn from_airborne_input(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary) -> Self {
     use CharacterState::*;
     if let Some(attack) = attacks.attack_to_execute(buffer, name, library, true) {
-      return self.buffer_attack(attack);
+      return self.buffer_air_attack(attack);
+    }
+
+    if movement.can_air_jump() && buffer.current_motion.y_positive() && !buffer.previous_motion.y_positive() {
+      match self {
+        AirDashing {busy:_, duration:_, velocity:_} | AirBackDashing {busy:_, duration:_, velocity:_} if !self.can_act_out_of_airdash() => (),
+        _ => {
+          movement.spend_air_jump();
+          return Self::buffer_air_jump(buffer.current_motion, movement);
+        }
+      }
     }
 
     if movement.can_airdash() {
@@ -302,15 +798,9 @@ impl CharacterState {
     }
 
     return match self {
-      AirDashing {busy:_, duration:_,velocity:_} => {
-        if self.is_finished_airdashing() {
-          Falling
-        } else {
-          self.clone()
-        }
-      },
-      AirBackDashing {busy:_, duration:_,velocity:_} => {
+      AirDashing {busy:_, duration:_,velocity:_} | AirBackDashing {busy:_, duration:_,velocity:_} => {
         if self.is_finished_airdashing() {
+          movement.airdash_fall = true;
           Falling
         } else {
           self.clone()
@@ -329,7 +819,12 @@ impl CharacterState {
 
   /// Returns an attacking state, with the passed attack
   fn buffer_attack(&self, attack: Attack) -> Self {
-    return CharacterState::Attacking {duration: attack.busy, attack: attack.clone(), cancellable: false}
+    return CharacterState::Attacking {duration: attack.busy, attack: attack.clone(), cancellable: false, hit_state: HitState::None}
+  }
+
+  /// Returns an airborne attacking state, with the passed attack
+  fn buffer_air_attack(&self, attack: Attack) -> Self {
+    return CharacterState::AttackingAirborne {duration: attack.busy, attack: attack.clone(), cancellable: false, hit_state: HitState::None}
   }
 
   /// Returns a backdashing state, based on movement
@@ -345,7 +840,19 @@ impl CharacterState {
         movement.set_interpolated_force(i_force);
         return CharacterState::BackDashing {duration: busy}
       },
-      _ => return Self::Idle
+      Teleport {busy, distance, motion_duration} => {
+        let vanish = motion_duration.max(1);
+        let velocity = Vec2::new(-distance * movement.facing_vector / vanish as f32, 0.0);
+        return CharacterState::BackTeleporting {duration: busy.max(vanish), vanish, velocity}
+      },
+      Leap {busy, motion_duration} => {
+        let airtime = motion_duration.max(MIN_BACKLEAP_AIRTIME) as f32;
+        movement.velocity = Vec2::new(
+          -movement.air_back_dash_speed * movement.facing_vector,
+          movement.gravity * airtime / 2.0
+        );
+        return CharacterState::BackLeaping {busy}
+      }
     }
   }
 
@@ -367,13 +874,13 @@ impl CharacterState {
     };
 
     let y_velocity = if superjump {
-      movement.jump_height * 1.25
+      movement.jump_height * movement.superjump_height
     } else {
       movement.jump_height
     };
     
     let velocity = Vec2::new(x_velocity, y_velocity);
-    return Self::Jumpsquat {duration: 3, velocity}
+    return Self::Jumpsquat {duration: 3, velocity, superjump}
   }
 
   /// Returns a Jumpsquat state from a neutral state, with a buffered jump based on character movement and input buffer
@@ -385,13 +892,25 @@ impl CharacterState {
     };
 
     let y_velocity = if superjump {
-      movement.jump_height * 1.25
+      movement.jump_height * movement.superjump_height
     } else {
       movement.jump_height
     };
     
     let velocity = Vec2::new(x_velocity, y_velocity);
-    return Self::Jumpsquat {duration: 3, velocity}
+    return Self::Jumpsquat {duration: 3, velocity, superjump}
+  }
+
+  /// Returns an AirJumpsquat state from an airborne state, with a buffered jump based on character movement and input buffer
+  fn buffer_air_jump(motion: u8, movement: &CharacterMovement) -> Self {
+    let x_velocity = match motion {
+      7 => movement.facing_vector * (-movement.back_walk_speed),
+      9 => movement.facing_vector * (movement.walk_speed),
+      _ => 0.0
+    };
+
+    let velocity = Vec2::new(x_velocity, movement.jump_height);
+    return Self::AirJumpsquat {duration: 1, velocity}
   }
 
   /// If the new state does not match the old state, generate an animation transition
@@ -409,22 +928,79 @@ impl CharacterState {
       (AirDashing {busy:_, duration:_, velocity:_}, Falling) => Some(AirdashToFall),
       (AirBackDashing {busy:_, duration:_, velocity:_}, Falling) => Some(AirbackdashToFall),
       (_, Idle) => Some(ToIdle),
-      (_, Jumpsquat {duration:_, velocity:_}) => Some(ToRise),
+      (_, Jumpsquat {duration:_, velocity:_, superjump: true}) => Some(ToSuperJump),
+      (_, Jumpsquat {duration:_, velocity:_, superjump: false}) => Some(ToRise),
+      (_, AirJumpsquat {duration:_, velocity:_}) => Some(ToAirJump),
       (_, Walking) => Some(ToWalk),
       (_, BackWalking) => Some(ToBackwalk),
       (_, Dashing) => Some(ToDash),
       (_, BackDashing {duration:_}) => Some(ToBackdash),
+      (_, BackTeleporting {duration:_, vanish:_, velocity:_}) => Some(ToBackTeleport),
+      (BackLeaping {busy:_}, BackLeapLanding {duration:_}) => Some(BackLeapToLand),
+      (_, LandingRecovery {duration:_}) => Some(ToLandingRecovery),
+      (_, BackLeaping {busy:_}) => Some(ToBackLeap),
       (_, AirDashing {busy:_, duration:_, velocity:_}) => Some(ToAirdash),
       (_, AirBackDashing {busy:_, duration:_, velocity:_}) => Some(ToAirBackdash),
       (_, Crouching) => Some(ToCrouch),
-      (_, Attacking {duration:_, attack, cancellable:_}) => Some(ToAttack {name: attack.name.clone()}),
+      (_, Hitstun {duration:_, hits:_}) => Some(ToHitstun),
+      (_, Blockstun {duration:_}) | (_, AirBlockstun {duration:_}) => Some(ToBlockstun),
+      (_, Juggle {duration:_, hits:_, wall_bounce:_, ground_bounce:_, hard_knockdown:_}) => Some(ToJuggle),
+      (AirTech {duration:_}, Falling) => Some(AirTechToFall),
+      (_, AirTech {duration:_}) => Some(ToAirTech),
+      (_, SoftKnockdown {duration:_}) => Some(ToSoftKnockdown),
+      (_, HardKnockdown {duration:_, delayed:_}) => Some(ToHardKnockdown),
+      (_, Wakeup {duration:_, quick_rise: true}) => Some(ToQuickRise),
+      (_, Wakeup {duration:_, quick_rise: false}) => Some(ToWakeup),
+      (_, Thrown {duration:_, tech:_, airborne:_}) => Some(ToThrown),
+      (_, ThrowTech {duration:_}) => Some(ToThrowTech),
+      (_, Attacking {duration:_, attack, cancellable:_, hit_state:_})
+      | (_, AttackingAirborne {duration:_, attack, cancellable:_, hit_state:_}) => Some(ToAttack {name: attack.name.clone()}),
       (_,_) => None
     }
   }
 
+  /// Is the state an attack on it's first frame, used to restart animations when cancelling from one attack into another
+  pub fn is_attack_startup(&self) -> bool {
+    match self {
+      CharacterState::Attacking {duration, attack, cancellable:_, hit_state:_}
+      | CharacterState::AttackingAirborne {duration, attack, cancellable:_, hit_state:_} => *duration == attack.busy,
+      _ => false
+    }
+  }
+
+  /// Record that the current attack hit or was blocked, opening up it's on hit and on block cancel routes
+  pub fn register_contact(&mut self, blocked: bool) {
+    if let CharacterState::Attacking {duration:_, attack:_, cancellable:_, hit_state}
+      | CharacterState::AttackingAirborne {duration:_, attack:_, cancellable:_, hit_state} = self {
+      // A hit always takes priority, a later blocked hitbox shouldn't close on hit routes
+      if *hit_state != HitState::Hit {
+        *hit_state = if blocked {
+          HitState::Blocked
+        } else {
+          HitState::Hit
+        };
+      }
+    }
+  }
+
+  /// Returns the movement the current attack applies to the character this frame
+  pub fn get_attack_movement_this_frame(&self) -> Option<AttackMovement> {
+    use CharacterState::*;
+    if let Attacking{duration, attack, cancellable: _, hit_state: _}
+      | AttackingAirborne{duration, attack, cancellable: _, hit_state: _} = self {
+      return attack.movement_events
+        .iter()
+        .rev()
+        .find(|m| (attack.busy as i16 - m.frame as i16) == *duration as i16)
+        .copied();
+    }
+    return None;
+  }
+
   pub fn get_hitbox_events_this_frame(&self) -> Option<Vec<HitboxEvent>> {
     use CharacterState::*;
-    if let Attacking{duration, attack, cancellable: _} = self.clone() {
+    if let Attacking{duration, attack, cancellable: _, hit_state: _}
+      | AttackingAirborne{duration, attack, cancellable: _, hit_state: _} = self.clone() {
       let mut events = Vec::new();
       for e in attack.hitbox_events.iter() {
         if (attack.busy as i8 - e.frame as i8) == duration as i8 {
@@ -441,6 +1017,95 @@ impl CharacterState {
     }
   }
 
+  /// Returns the throw to grab with this frame, if the current attack is a throw
+  pub fn get_throw_this_frame(&self) -> Option<Throw> {
+    use CharacterState::*;
+    if let Attacking{duration, attack, cancellable: _, hit_state: _}
+      | AttackingAirborne{duration, attack, cancellable: _, hit_state: _} = self {
+      if let Some(throw) = attack.throw {
+        if (attack.busy as i16 - throw.frame as i16) == *duration as i16 {
+          return Some(throw);
+        }
+      }
+    }
+    return None;
+  }
+
+  /// Returns the throw to release the opponent from this frame, if the character is throwing
+  pub fn get_throw_release_this_frame(&self) -> Option<Throw> {
+    use CharacterState::*;
+    if let Throwing{duration, throw} = self {
+      if (throw.duration as i16 - throw.release as i16) == *duration as i16 {
+        return Some(*throw);
+      }
+    }
+    return None;
+  }
+
+  /// Returns the number of hits the character has taken since they could last act
+  pub fn get_combo_hits(&self) -> u8 {
+    use CharacterState::*;
+    match self {
+      Hitstun {duration:_, hits} => return *hits,
+      Juggle {duration:_, hits, wall_bounce:_, ground_bounce:_, hard_knockdown:_} => return *hits,
+      _ => return 0
+    }
+  }
+
+  /// Returns how the character is blocking, based on current state and the direction being held
+  pub fn get_block_state(&self, motion: u8) -> BlockState {
+    use CharacterState::*;
+    let guarding = match self {
+      Blockstun {duration:_} | AirBlockstun {duration:_} => true,
+      _ => motion.x_negative()
+    };
+
+    if !guarding {
+      return BlockState::None;
+    }
+
+    match self {
+      Idle
+      | Walking
+      | BackWalking
+      | Crouching
+      | Blockstun {duration:_} => {
+        if motion.y_negative() {
+          return BlockState::Crouch {barrier: false, instant: false};
+        } else {
+          return BlockState::Stand {barrier: false, instant: false};
+        }
+      },
+      Rising {busy:_}
+      | Falling
+      | AirBlockstun {duration:_} => return BlockState::Air {barrier: false, instant: false},
+      _ => return BlockState::None
+    }
+  }
+
+  /// Returns whether or not the character can be grabbed, based on current state
+  pub fn is_throwable(&self) -> bool {
+    use CharacterState::*;
+    if self.is_invulnerable() {
+      return false;
+    }
+    if let Some((attack, frame)) = self.get_current_attack() {
+      if attack.is_throw_invulnerable(frame) {
+        return false;
+      }
+    }
+    match self {
+      Hitstun {duration:_, hits:_}
+      | Blockstun {duration:_}
+      | AirBlockstun {duration:_}
+      | Juggle {duration:_, hits:_, wall_bounce:_, ground_bounce:_, hard_knockdown:_}
+      | Throwing {duration:_, throw:_}
+      | Thrown {duration:_, tech:_, airborne:_}
+      | ThrowTech {duration:_} => return false,
+      _ => return true
+    }
+  }
+
   /// Returns whether or not the character can turn around, based on current state
   pub fn get_can_turn(&self) -> bool {
     use CharacterState::*;
@@ -462,12 +1127,147 @@ impl CharacterState {
       AirJumpsquat {duration:_, velocity:_}
       | Rising {busy:_}
       | Falling
+      | AirBlockstun {duration:_}
+      | Juggle {duration:_, hits:_, wall_bounce:_, ground_bounce:_, hard_knockdown:_}
+      | AirTech {duration:_}
+      | BackLeaping {busy:_}
+      | AttackingAirborne {duration:_, attack:_, cancellable:_, hit_state:_}
       | AirDashing {busy:_, duration:_, velocity:_}
-      | AirBackDashing {busy:_, duration:_, velocity:_} => return true,
+      | AirBackDashing {busy:_, duration:_, velocity:_}
+      | Thrown {duration:_, tech:_, airborne: true} => return true,
+      _ => return false
+    }
+  }
+
+  /// Did the character just touch down, coming from `previous`. Throws pull characters out of the air
+  /// without landing them, they land once the throw is over
+  pub fn has_landed(&self, previous: &Self) -> bool {
+    use CharacterState::*;
+    match self {
+      Throwing {duration:_, throw:_} | Thrown {duration:_, tech:_, airborne:_} | ThrowTech {duration:_} => return false,
+      _ => return previous.get_airborne() && !self.get_airborne()
+    }
+  }
+
+  /// Is the character walking, dashing or airdashing towards their opponent
+  pub fn is_moving_forward(&self) -> bool {
+    use CharacterState::*;
+    match self {
+      Walking | Dashing | AirDashing {busy:_, duration:_, velocity:_} => true,
+      _ => false
+    }
+  }
+
+  /// Is the character in an attack that hasn't made contact yet
+  pub fn is_whiffing(&self) -> bool {
+    use CharacterState::*;
+    match self {
+      Attacking {duration:_, attack:_, cancellable:_, hit_state}
+      | AttackingAirborne {duration:_, attack:_, cancellable:_, hit_state} => *hit_state == HitState::None,
+      _ => false
+    }
+  }
+
+  /// Returns whether or not the character is hidden from view, based on current state
+  pub fn is_vanished(&self) -> bool {
+    use CharacterState::*;
+    match self {
+      BackTeleporting {duration:_, vanish, velocity:_} => return *vanish > 0,
       _ => return false
     }
   }
 
+  /// Returns whether or not the character can be hit, based on current state
+  pub fn is_invulnerable(&self) -> bool {
+    use CharacterState::*;
+    match self {
+      BackTeleporting {duration:_, vanish, velocity:_} => return *vanish > 0,
+      AirTech {duration:_}
+      | SoftKnockdown {duration:_}
+      | HardKnockdown {duration:_, delayed:_}
+      | Wakeup {duration:_, quick_rise:_} => return true,
+      _ => return false
+    }
+  }
+
+  /// Returns whether or not the character is still stuck in the opponent's combo
+  pub fn is_in_combo(&self) -> bool {
+    use CharacterState::*;
+    match self {
+      Hitstun {duration:_, hits:_}
+      | Juggle {duration:_, hits:_, wall_bounce:_, ground_bounce:_, hard_knockdown:_}
+      | SoftKnockdown {duration:_}
+      | HardKnockdown {duration:_, delayed:_}
+      | Thrown {duration:_, tech:_, airborne:_} => return true,
+      _ => return false
+    }
+  }
+
+  /// Returns whether or not the character has been hit or thrown out of what they were doing
+  pub fn is_interrupted(&self) -> bool {
+    use CharacterState::*;
+    match self {
+      Hitstun {duration:_, hits:_}
+      | Blockstun {duration:_}
+      | AirBlockstun {duration:_}
+      | Juggle {duration:_, hits:_, wall_bounce:_, ground_bounce:_, hard_knockdown:_}
+      | Thrown {duration:_, tech:_, airborne:_}
+      | ThrowTech {duration:_} => return true,
+      _ => return false
+    }
+  }
+
+  /// Returns the attack being performed and how many frames of it have elapsed
+  pub fn get_current_attack(&self) -> Option<(&Attack, u8)> {
+    use CharacterState::*;
+    match self {
+      Attacking {duration, attack, cancellable:_, hit_state:_}
+      | AttackingAirborne {duration, attack, cancellable:_, hit_state:_} => Some((attack, attack.current_frame(*duration))),
+      _ => None
+    }
+  }
+
+  /// Returns whether or not the current attack makes the character immune to strikes
+  pub fn is_strike_invulnerable(&self) -> bool {
+    match self.get_current_attack() {
+      Some((attack, frame)) => attack.is_strike_invulnerable(frame),
+      None => false
+    }
+  }
+
+  /// Returns whether or not the current attack makes the character immune to projectiles
+  pub fn is_projectile_invulnerable(&self) -> bool {
+    match self.get_current_attack() {
+      Some((attack, frame)) => attack.is_projectile_invulnerable(frame),
+      None => false
+    }
+  }
+
+  /// Returns whether or not the current attack will absorb the next hit
+  pub fn is_armored(&self) -> bool {
+    match self.get_current_attack() {
+      Some((attack, frame)) => attack.is_armored(frame),
+      None => false
+    }
+  }
+
+  /// Returns what kind of counter hit getting hit right now would be
+  pub fn get_counter_hit(&self) -> Option<CounterHit> {
+    match self.get_current_attack() {
+      Some((attack, frame)) => attack.get_counter_hit(frame),
+      None => None
+    }
+  }
+
+  /// Spend one hit of the current attack's armor
+  pub fn absorb_hit(&mut self) {
+    use CharacterState::*;
+    if let Attacking {duration:_, attack, cancellable:_, hit_state:_}
+      | AttackingAirborne {duration:_, attack, cancellable:_, hit_state:_} = self {
+      attack.absorb_hit();
+    }
+  }
+
   pub fn is_finished_airdashing(&self) -> bool {
     use CharacterState::*;
     match self {
@@ -486,10 +1286,38 @@ impl CharacterState {
     }
   }
 
-  /// Called when the character lands, forcing them into a Idle state
-  pub fn land(&mut self) {
+  /// Called when the character lands, forcing them into landing recovery
+  pub fn land(&mut self, movement: &CharacterMovement) {
+    *self = self.landing_state(movement);
+  }
+
+  /// Returns the state the character lands into, based on how they were airborne
+  pub fn landing_state(&self, movement: &CharacterMovement) -> Self {
+    use CharacterState::*;
+    let duration = match self {
+      AirDashing {busy:_, duration:_, velocity:_} | AirBackDashing {busy:_, duration:_, velocity:_} => movement.landing.airdash,
+      Falling if movement.airdash_fall => movement.landing.airdash,
+      AttackingAirborne {duration:_, attack, cancellable:_, hit_state: HitState::None} => attack.landing_recovery.unwrap_or(movement.landing.air_attack),
+      _ => movement.landing.jump,
+    };
+    if duration == 0 {
+      return Idle;
+    }
+    return LandingRecovery {duration};
+  }
+
+  /// Returns a new state from landing recovery, acting normally once it's over
+  pub fn from_landing_recovery(&self, buffer: &mut FighterInputBuffer, movement: &mut CharacterMovement, attacks: &mut CharacterAttacks, name: &Name, library: &CharacterLibrary) -> Self {
     use CharacterState::*;
-    *self = Idle;
+    match self {
+      LandingRecovery {duration} => {
+        if *duration == 0 {
+          return self.from_neutral_states(buffer, movement, attacks, name, library);
+        }
+        return self.clone();
+      },
+      _ => return self.clone(),
+    }
   }
 }
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
@@ -497,6 +1325,8 @@ impl CharacterState {
 pub struct CharacterAttacks {
   pub all_attacks: Vec<String>,
   pub available_attacks: Vec<String>,
+  /// Meter the character can spend, attacks that cost more can't be performed
+  pub available_meter: u16,
 }
 
 impl CharacterAttacks {
@@ -511,7 +1341,8 @@ impl CharacterAttacks {
     }
     CharacterAttacks {
       all_attacks: attack_names.clone(),
-      available_attacks: attack_names.clone()
+      available_attacks: attack_names.clone(),
+      available_meter: 0
     }
   }
 
@@ -519,28 +1350,75 @@ impl CharacterAttacks {
     self.available_attacks = self.all_attacks.clone();
   }
 
-  pub fn attack_to_execute(&mut self,  buffer: &FighterInputBuffer, name: &Name, character_library: &CharacterLibrary, _airborne: bool) -> Option<Attack> {
+  /// Returns the attack the buffer is asking for, only considering attacks that can be performed grounded or airborne
+  pub fn attack_to_execute(&mut self,  buffer: &mut FighterInputBuffer, name: &Name, character_library: &CharacterLibrary, airborne: bool) -> Option<Attack> {
     if buffer.current_press.any_pressed() {
-      return self.find_attack(buffer, name, character_library);
+      return self.find_attack_where(buffer, name, character_library, |attack| attack.stance.allows(airborne));
     } else {
       return None;
     }
   }
 
+  /// Is a throw button being pressed, without consuming any buffered command motion. Used to break throws
+  pub fn has_throw_pressed(&self, buffer: &FighterInputBuffer, name: &Name, character_library: &CharacterLibrary, airborne: bool) -> bool {
+    let buttons = buffer.current_press.to_string();
+    for attack_name in self.available_attacks.iter() {
+      if !buttons.chars().any(|button| attack_name.ends_with(button)) {
+        continue;
+      }
+      let full_attack_name = format!("{}_{}", name.as_str(), attack_name);
+      if let Some(attack) = character_library.find_attack(full_attack_name) {
+        if attack.throw.is_some() && attack.command.is_none() && attack.stance.allows(airborne) {
+          return true;
+        }
+      }
+    }
+    return false;
+  }
+
   // pub fn best_match_attack_name(&self, buffer: &FighterInputBuffer) -> Option<String> {
 
   // }
 
-  pub fn find_attack(&mut self, buffer: &FighterInputBuffer, name: &Name, character_library: &CharacterLibrary) -> Option<Attack> {
+  /// Finds the attack matching the buffer, special moves whose command was detected by the buffer are
+  /// preferred over normals, and consume the command motion when they are found
+  pub fn find_attack(&mut self, buffer: &mut FighterInputBuffer, name: &Name, character_library: &CharacterLibrary) -> Option<Attack> {
+    self.find_attack_where(buffer, name, character_library, |_| true)
+  }
+
+  /// Finds the attack matching the buffer, only considering attacks that pass the predicate
+  pub fn find_attack_where<F: Fn(&Attack) -> bool>(&mut self, buffer: &mut FighterInputBuffer, name: &Name, character_library: &CharacterLibrary, predicate: F) -> Option<Attack> {
     let motion = buffer.current_motion; 
     let buttons = buffer.current_press.to_string();
+
+    if let Some(command) = buffer.command_type {
+      for button in buttons.chars().rev() {
+        for attack_name in self.available_attacks.iter() {
+          if !attack_name.ends_with(button) {
+            continue;
+          }
+          let full_attack_name = format!("{}_{}", name.as_str(), attack_name);
+          if let Some(attack) = character_library.find_attack(full_attack_name) {
+            if attack.command == Some(command) && attack.meter_cost <= self.available_meter && predicate(&attack) {
+              buffer.consume_motion();
+              return Some(attack);
+            }
+          }
+        }
+      }
+    }
+
     let mut current_regex: Regex;
     for button in buttons.chars().rev() {
       current_regex = Regex::new(&format!("({}).*({})", motion, button)[..]).unwrap();
       for attack_name in self.available_attacks.iter() {
         if current_regex.is_match(attack_name) {
           let full_attack_name = format!("{}_{}", name.as_str(), attack_name);
-          return character_library.find_attack(full_attack_name);
+          if let Some(attack) = character_library.find_attack(full_attack_name) {
+            if attack.command.is_none() && attack.meter_cost <= self.available_meter && predicate(&attack) {
+              return Some(attack);
+            }
+          }
         }
       }
     }
@@ -564,13 +1442,20 @@ pub struct CharacterMovement {
   pub dash_speed: f32,
   pub gravity: f32,
   pub jump_height: f32,
+  pub superjump_height: f32,
+  pub superjump_air_jumps: u8,
+  pub superjump_airdashes: u8,
   pub max_airdash_time: u8,
   pub max_air_backdash_time: u8,
   pub backdash: Backdash,
+  pub wakeup: WakeupTimings,
+  pub landing: LandingTimings,
   pub facing_vector: f32,
   pub velocity: Vec2,
   pub interpolated_force: Option<InterpolatedForce>,
   pub can_turn: bool,
+  /// Is the character falling out of an airdash, so it lands with airdash recovery
+  pub airdash_fall: bool,
 }
 
 impl CharacterMovement {
@@ -588,15 +1473,21 @@ impl CharacterMovement {
       dash_speed: s.dash_speed,
       gravity: s.gravity,
       jump_height: s.jump_height,
+      superjump_height: s.superjump_height,
+      superjump_air_jumps: s.superjump_air_jumps,
+      superjump_airdashes: s.superjump_airdashes,
       max_airdash_time: s.max_airdash_time,
       max_air_backdash_time: s.max_air_backdash_time,
       backdash: s.backdash,
+      wakeup: s.wakeup,
+      landing: s.landing,
       facing_vector: 1.0,
       air_jumps_remaining: s.air_jumps,
       airdashes_remaining: s.airdashes,
       velocity: Vec2::ZERO,
       interpolated_force: None,
       can_turn: true,
+      airdash_fall: false,
     }
   }
   pub fn determine_velocity(&mut self, state: &CharacterState) {
@@ -604,9 +1495,24 @@ impl CharacterMovement {
     self.velocity = match state {
       Walking => Vec2::X * self.facing_vector * self.walk_speed,
       BackWalking => Vec2::X * -self.facing_vector * self.walk_speed,
-      Rising {busy:_} | Falling | Juggle => self.velocity - (Vec2::Y * self.gravity),
+      Rising {busy:_} | Falling | AirBlockstun {duration:_} | AirTech {duration:_} | BackLeaping {busy:_} => self.velocity - (Vec2::Y * self.gravity),
+      AttackingAirborne {duration:_, attack:_, cancellable:_, hit_state:_} => self.velocity - (Vec2::Y * self.gravity),
+      Juggle {duration:_, hits, wall_bounce:_, ground_bounce:_, hard_knockdown:_} => {
+        let gravity_scaling = 1.0 + (*hits as f32 * JUGGLE_GRAVITY_SCALING);
+        self.velocity - (Vec2::Y * self.gravity * gravity_scaling)
+      },
       Dashing => Vec2::X * self.facing_vector * self.dash_speed,
-      BackDashing {duration:_} => Vec2::ZERO,
+      BackDashing {duration:_} | BackLeapLanding {duration:_} | LandingRecovery {duration:_} => Vec2::ZERO,
+      Hitstun {duration:_, hits:_} | Blockstun {duration:_} => Vec2::ZERO,
+      SoftKnockdown {duration:_} | HardKnockdown {duration:_, delayed:_} | Wakeup {duration:_, quick_rise:_} => Vec2::ZERO,
+      Throwing {duration:_, throw:_} | Thrown {duration:_, tech:_, airborne:_} | ThrowTech {duration:_} => Vec2::ZERO,
+      BackTeleporting {duration:_, vanish, velocity} => {
+        if *vanish > 0 {
+          *velocity
+        } else {
+          Vec2::ZERO
+        }
+      },
       AirDashing {busy:_, duration:_, velocity} => *velocity,
       AirBackDashing {busy:_, duration:_, velocity} => *velocity,
       _ => self.velocity.custom_lerp(Vec2::ZERO, 0.5)
@@ -627,6 +1533,15 @@ impl CharacterMovement {
     return self.velocity.y < 0.0;
   }
 
+  pub fn can_air_jump(&self) -> bool {
+    return self.air_jumps_remaining > 0;
+  }
+
+  pub fn spend_air_jump(&mut self) {
+    self.air_jumps_remaining = countdown(self.air_jumps_remaining);
+    self.airdash_fall = false;
+  }
+
   pub fn can_airdash(&self) -> bool {
     return self.airdashes_remaining > 0;
   }
@@ -638,11 +1553,27 @@ impl CharacterMovement {
   pub fn land(&mut self) {
     self.air_jumps_remaining = self.air_jumps;
     self.airdashes_remaining = self.airdashes;
+    self.airdash_fall = false;
+  }
+
+  /// Adds the extra air options a character gets when leaving the ground with a super jump
+  pub fn grant_superjump_air_options(&mut self) {
+    self.air_jumps_remaining += self.superjump_air_jumps;
+    self.airdashes_remaining += self.superjump_airdashes;
   }
 
   pub fn set_interpolated_force(&mut self, i_force: InterpolatedForce) {
     self.interpolated_force = Some(i_force);
   }
+
+  /// Moves the character with a force from their current attack
+  pub fn apply_attack_movement(&mut self, attack_movement: AttackMovement) {
+    if attack_movement.reset_velocity {
+      self.velocity = Vec2::ZERO;
+    }
+    let i_force = attack_movement.to_interpolated_force(self.facing_vector);
+    self.set_interpolated_force(i_force);
+  }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Component)]
@@ -657,6 +1588,16 @@ impl Default for PlayerId {
   }
 }
 
+impl PlayerId {
+  /// Returns the id of the other player
+  pub fn opponent(&self) -> PlayerId {
+    match self {
+      PlayerId::P1 => PlayerId::P2,
+      PlayerId::P2 => PlayerId::P1,
+    }
+  }
+}
+
 #[derive(Serialize,Deserialize)]
 pub struct CharacterMovementSerialized {
   pub jumpsquat: u8,
@@ -669,9 +1610,26 @@ pub struct CharacterMovementSerialized {
   pub dash_speed: f32,
   pub gravity: f32,
   pub jump_height: f32,
+  /// Multiplier applied to jump_height when super jumping
+  #[serde(default = "default_superjump_height")]
+  pub superjump_height: f32,
+  /// Extra air jumps granted by a super jump
+  #[serde(default)]
+  pub superjump_air_jumps: u8,
+  /// Extra airdashes granted by a super jump
+  #[serde(default)]
+  pub superjump_airdashes: u8,
   pub max_airdash_time: u8,
   pub max_air_backdash_time: u8,
-  pub backdash: Backdash
+  pub backdash: Backdash,
+  #[serde(default)]
+  pub wakeup: WakeupTimings,
+  #[serde(default)]
+  pub landing: LandingTimings
+}
+
+fn default_superjump_height() -> f32 {
+  1.25
 }
 
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
@@ -698,6 +1656,61 @@ impl Backdash {
   }
 }
 
+/// Frame data for knockdowns, getting up, and recovering in the air
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WakeupTimings {
+  /// Frames spent on the ground after a soft knockdown
+  pub soft_knockdown: u8,
+  /// Frames spent on the ground after a hard knockdown
+  pub hard_knockdown: u8,
+  /// Frames spent getting up when quick rising out of a soft knockdown
+  pub quick_rise: u8,
+  /// Frames added to a hard knockdown when the character delays their wakeup
+  pub delayed_wakeup: u8,
+  /// Frames spent getting up after a knockdown
+  pub wakeup: u8,
+  /// Frames spent air teching before the character can act
+  pub air_tech: u8,
+}
+
+impl Default for WakeupTimings {
+  fn default() -> Self {
+    WakeupTimings {
+      soft_knockdown: 20,
+      hard_knockdown: 45,
+      quick_rise: 10,
+      delayed_wakeup: 20,
+      wakeup: 12,
+      air_tech: 14,
+    }
+  }
+}
+
+/// Frame data for recovering after landing from the air
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LandingTimings {
+  /// Frames spent landing from a jump
+  pub jump: u8,
+  /// Frames spent landing from an airdash or air backdash
+  pub airdash: u8,
+  /// Frames spent landing from a whiffed air attack, when the attack doesn't set it's own
+  pub air_attack: u8,
+}
+
+impl Default for LandingTimings {
+  fn default() -> Self {
+    LandingTimings {
+      jump: 3,
+      airdash: 5,
+      air_attack: 6,
+    }
+  }
+}
+
 #[derive(Bundle, Default)]
 pub struct FighterCharacterBundle {
   pub sprite: TextureAtlasSprite,
@@ -709,16 +1722,19 @@ pub struct FighterCharacterBundle {
   pub state: CharacterState,
   pub movement: CharacterMovement,
   pub attacks: CharacterAttacks,
+  pub hurtbox: Hurtbox,
+  pub pushbox: Pushbox,
+  pub hit_groups: HitGroups,
+  pub health: Health,
+  pub combo_tracker: ComboTracker,
+  pub meter: SuperMeter,
   pub animation_controller: AnimationController,
   pub name: Name,
 }
 
 impl FighterCharacterBundle {
-  pub fn new(player_id: PlayerId, character_prefix: &str, library: &CharacterLibrary) -> Self {
-    let transform = match player_id {
-      PlayerId::P1 => Transform::from_xyz(-40.0,0.0,0.0),
-      PlayerId::P2 => Transform::from_xyz(40.0,0.0,0.0),
-    };
+  pub fn new(player_id: PlayerId, character_prefix: &str, library: &CharacterLibrary, round_config: &RoundConfig) -> Self {
+    let transform = Transform::from_translation(round_config.starting_position(&player_id, 1));
 
     let movement = library.get_movement(character_prefix).unwrap();
     let texture_atlas = library.get_atlas(character_prefix).unwrap();
@@ -729,6 +1745,9 @@ impl FighterCharacterBundle {
       transform,
       player_id,
       animation_controller: AnimationController::new(character_prefix, library),
+      health: Health::new(library.get_health(character_prefix).unwrap_or(DEFAULT_HEALTH)),
+      meter: SuperMeter::new(library.get_meter_gain(character_prefix).unwrap_or_default()),
+      pushbox: library.get_pushbox(character_prefix).unwrap_or_default(),
       attacks,
       name: Name::new(character_prefix.to_owned()),
       ..Default::default()
@@ -740,18 +1759,40 @@ impl FighterCharacterBundle {
 pub fn manage_character_state(
   mut player_data: ResMut<PlayerData>,
   library: Res<CharacterLibrary>, 
-  mut query: Query<(&PlayerId, &Name, &mut CharacterState, &mut CharacterMovement, &mut CharacterAttacks)>,
+  mut superflash: ResMut<Superflash>,
+  bounds: Res<StageBounds>,
+  mut query: Query<(&PlayerId, &Name, &mut CharacterState, &mut CharacterMovement, &mut CharacterAttacks, &mut HitGroups, &mut SuperMeter)>,
   mut transition_writer: EventWriter<AnimationTransitionEvent>,
+  mut superflash_writer: EventWriter<SuperflashEvent>,
 ) {
-  for (player_id, name, mut state, mut movement, mut attacks) in query.iter_mut() {
+  for (player_id, name, mut state, mut movement, mut attacks, mut hit_groups, mut meter) in query.iter_mut() {
     let position = player_data.get_position(player_id);
     for buffer in player_data.buffers.iter_mut() {
       if buffer.player_id == *player_id {
-        let transition = state.update(buffer,&mut movement, &mut attacks, name, &library, position);
-        if let Some(t) = transition {
-            if t == AnimationTransition::FallToIdle {
-              movement.land();
+        let whiffing = state.is_whiffing();
+        let previous_state = state.clone();
+        attacks.available_meter = meter.get_current();
+        let transition = state.update(buffer,&mut movement, &mut attacks, name, &library, position, &bounds);
+        // The previous attack ended or was cancelled without ever making contact
+        if whiffing && (state.get_current_attack().is_none() || state.is_attack_startup()) {
+          let amount = meter.gain.on_whiff;
+          meter.add(amount);
+        }
+        if state.is_attack_startup() {
+          hit_groups.clear();
+          if let Some((attack, _)) = state.get_current_attack() {
+            meter.spend(attack.meter_cost);
+            if let Some(duration) = attack.superflash {
+              superflash.start(*player_id, duration);
+              superflash_writer.send(SuperflashEvent {player_id: *player_id, duration});
             }
+          }
+        }
+        // Air options come back when the character touches down
+        if state.has_landed(&previous_state) {
+          movement.land();
+        }
+        if let Some(t) = transition {
           transition_writer.send(
       AnimationTransitionEvent {
               player_id: *player_id,
@@ -770,6 +1811,9 @@ pub fn manage_character_velocity (
 ) {
   for(state, mut movement) in query.iter_mut() {
     movement.determine_velocity(state);
+    if let Some(attack_movement) = state.get_attack_movement_this_frame() {
+      movement.apply_attack_movement(attack_movement);
+    }
     movement.can_turn = state.get_can_turn();
   }
 }
@@ -777,6 +1821,7 @@ pub fn manage_character_velocity (
 /// Apply player velocity
 pub fn apply_character_velocity (
   mut player_data: ResMut<PlayerData>, 
+  bounds: Res<StageBounds>,
   mut query: Query<(&PlayerId, &mut CharacterMovement, &mut Transform, &mut TextureAtlasSprite)>,
 ) {
   for(player_id, mut movement, mut transform, mut sprite) in query.iter_mut() {
@@ -785,6 +1830,7 @@ pub fn apply_character_velocity (
     if transform.translation.y < 0.0 {
       transform.translation.y = 0.0;
     }
+    transform.translation.x = bounds.clamp(transform.translation.x);
 
     player_data.set_position(player_id, transform.translation);
     let facing_vector = player_data.get_facing_vector(player_id);
@@ -795,49 +1841,190 @@ pub fn apply_character_velocity (
   }
 }
 
-fn character_landing(state: &mut CharacterState, movement: &mut CharacterMovement) {
-  state.land();
-  movement.land();
-} 
-
-pub fn update_debug_ui(
-  mut q: QuerySet<(
-    QueryState<(&mut Text, &PlayerId)>,
-    QueryState<&CharacterMovement>
-  )>,
-  diagnostics: Res<Diagnostics>,
-  player_data: Res<PlayerData>
+/// Push characters whose pushboxes overlap away from each other
+pub fn separate_characters(
+  mut player_data: ResMut<PlayerData>,
+  bounds: Res<StageBounds>,
+  mut query: Query<(&PlayerId, &Pushbox, &CharacterState, &CharacterMovement, &mut Transform)>,
 ) {
-  let distance = player_data.get_distance();
-  let mut player_text: Vec<Vec<String>> = Vec::new();
-  let mut fps = 0.0;
-  if let Some(fps_diagnostic) = diagnostics.get(FrameTimeDiagnosticsPlugin::FPS) {
-      if let Some(fps_avg) = fps_diagnostic.average() {
-          fps = fps_avg;
-      }
-  }
-
-  for movement in q.q1().iter() {
-    let mut my_strings: Vec<String> = Vec::new();  
-    my_strings.push(format!("Airdashes: {:?} \n", movement.airdashes_remaining));
-    my_strings.push(format!("Velocity: {:?} \n", movement.velocity));
-    my_strings.push(format!("Facing Vector: {:?} \n", movement.facing_vector));
-    my_strings.push(format!("FPS: {:.1} \n", fps));
-    my_strings.push(format!("Distance: {:?}", distance));
-    let strings_to_push = my_strings.clone();
-    player_text.push(strings_to_push);
-  }
-
-  for (mut text, player_id) in q.q0().iter_mut() {
-    let index = match player_id {
-      PlayerId::P1 => 0,
-      PlayerId::P2 => 1
-    };
-      text.sections[0].value = player_text[index][0].clone();
-      text.sections[1].value = player_text[index][1].clone();
-      text.sections[2].value = player_text[index][2].clone();
-      text.sections[3].value = player_text[index][3].clone();
-      text.sections[4].value = player_text[index][4].clone();
+  let mut combinations = query.iter_combinations_mut();
+  while let Some([(a_id, a_box, a_state, a_movement, mut a_transform), (b_id, b_box, b_state, b_movement, mut b_transform)]) = combinations.fetch_next() {
+    if a_state.is_vanished() || b_state.is_vanished() {
+      continue;
+    }
+    let a_center = a_box.get_center(a_transform.translation, a_movement.facing_vector);
+    let b_center = b_box.get_center(b_transform.translation, b_movement.facing_vector);
+    let overlap = a_box.get_overlap(a_center, b_box, b_center);
+    if overlap <= 0.0 {
+      continue;
+    }
+    let (a_x, b_x) = push_apart(a_transform.translation.x, b_transform.translation.x, overlap, &bounds);
+    a_transform.translation.x = a_x;
+    b_transform.translation.x = b_x;
+    player_data.set_position(a_id, a_transform.translation);
+    player_data.set_position(b_id, b_transform.translation);
+  }
+}
+
+/// Apply hits and blocks to the characters on the receiving end of collisions
+pub fn apply_collisions(
+  mut collision_reader: EventReader<CollisionEvent>,
+  mut query: Query<(&PlayerId, &mut CharacterState, &mut CharacterMovement)>,
+  mut transition_writer: EventWriter<AnimationTransitionEvent>,
+  mut counter_hit_writer: EventWriter<CounterHitEvent>,
+) {
+  // Throws are resolved first, a strike from a character who was thrown on the same frame loses to the throw
+  let thrown: Vec<PlayerId> = query
+    .iter()
+    .filter(|(_, state, _)| matches!(state, CharacterState::Thrown {duration:_, tech:_, airborne:_}))
+    .map(|(player_id, _, _)| *player_id)
+    .collect();
+
+  for event in collision_reader.iter() {
+    if thrown.contains(&event.player_id) && !event.collision.hitbox.is_projectile() {
+      continue;
+    }
+    for (player_id, mut state, mut movement) in query.iter_mut() {
+      // Projectiles connect independently of whatever their owner is doing now
+      if *player_id == event.player_id && !event.collision.hitbox.is_projectile() {
+        state.register_contact(event.collision.blocked);
+      }
+      if *player_id == event.recieving_player_id {
+        if event.collision.armored {
+          state.absorb_hit();
+          continue;
+        }
+        if let Some(counter_hit) = event.collision.counter_hit {
+          counter_hit_writer.send(CounterHitEvent {
+            counter_hit,
+            player_id: event.player_id,
+            recieving_player_id: event.recieving_player_id,
+          });
+        }
+        let new_state = state.take_hit(&event.collision, &mut movement);
+        if let Some(t) = state.calculate_transition(&new_state) {
+          transition_writer.send(AnimationTransitionEvent::new(*player_id, t));
+        }
+        *state = new_state;
+      }
+    }
+  }
+}
+
+/// Put the thrower and the character they grabbed into their throw states,
+/// characters who throw each other on the same frame both tech instead
+pub fn apply_throws(
+  mut throw_reader: EventReader<ThrowEvent>,
+  mut query: Query<(&PlayerId, &mut CharacterState, &mut CharacterMovement)>,
+  mut transition_writer: EventWriter<AnimationTransitionEvent>,
+) {
+  let events: Vec<&ThrowEvent> = throw_reader.iter().collect();
+  let mutual: Vec<PlayerId> = events
+    .iter()
+    .filter(|e| events.iter().any(|other| other.player_id == e.recieving_player_id && other.recieving_player_id == e.player_id))
+    .map(|e| e.player_id)
+    .collect();
+
+  for (player_id, mut state, mut movement) in query.iter_mut() {
+    if mutual.contains(player_id) {
+      let new_state = state.break_throw(&mut movement);
+      if let Some(t) = state.calculate_transition(&new_state) {
+        transition_writer.send(AnimationTransitionEvent::new(*player_id, t));
+      }
+      *state = new_state;
+    }
+  }
+
+  for event in events.into_iter().filter(|e| !mutual.contains(&e.player_id)) {
+    let mut attack_name: Option<String> = None;
+    let mut grabbable = false;
+    for (player_id, state, _) in query.iter_mut() {
+      if *player_id == event.player_id {
+        if let CharacterState::Attacking {duration:_, attack, cancellable:_, hit_state:_}
+          | CharacterState::AttackingAirborne {duration:_, attack, cancellable:_, hit_state:_} = &*state {
+          attack_name = Some(attack.name.clone());
+        }
+      }
+      if *player_id == event.recieving_player_id {
+        grabbable = state.is_throwable();
+      }
+    }
+
+    if let (Some(name), true) = (attack_name, grabbable) {
+      for (player_id, mut state, mut movement) in query.iter_mut() {
+        if *player_id == event.player_id {
+          movement.velocity = Vec2::ZERO;
+          movement.interpolated_force = None;
+          *state = CharacterState::Throwing {duration: event.throw.duration, throw: event.throw};
+          transition_writer.send(AnimationTransitionEvent::new(*player_id, AnimationTransition::ToThrow {name: name.clone()}));
+        }
+        if *player_id == event.recieving_player_id {
+          movement.velocity = Vec2::ZERO;
+          movement.interpolated_force = None;
+          *state = CharacterState::Thrown {duration: event.throw.duration, tech: event.throw.tech_window, airborne: event.throw.air};
+          transition_writer.send(AnimationTransitionEvent::new(*player_id, AnimationTransition::ToThrown));
+        }
+      }
+    }
+  }
+}
+
+/// Break the throw for a thrower when the character they grabbed techs it
+pub fn sync_throw_techs(
+  mut query: Query<(&PlayerId, &mut CharacterState, &mut CharacterMovement)>,
+  mut transition_writer: EventWriter<AnimationTransitionEvent>,
+) {
+  let mut teched: Vec<PlayerId> = Vec::new();
+  for (player_id, state, _) in query.iter_mut() {
+    if let CharacterState::ThrowTech {duration:_} = *state {
+      teched.push(*player_id);
+    }
+  }
+
+  for (player_id, mut state, mut movement) in query.iter_mut() {
+    if let CharacterState::Throwing {duration:_, throw:_} = *state {
+      if teched.contains(&player_id.opponent()) {
+        let new_state = state.break_throw(&mut movement);
+        if let Some(t) = state.calculate_transition(&new_state) {
+          transition_writer.send(AnimationTransitionEvent::new(*player_id, t));
+        }
+        *state = new_state;
+      }
+    }
+  }
+}
+
+/// Keep each character's hurtbox in sync with their state and the direction they are holding
+pub fn update_hurtboxes(
+  player_data: Res<PlayerData>,
+  mut query: Query<(&PlayerId, &CharacterState, &mut Hurtbox)>,
+) {
+  for (player_id, state, mut hurtbox) in query.iter_mut() {
+    for buffer in player_data.buffers.iter() {
+      if buffer.player_id == *player_id {
+        hurtbox.is_grounded = !state.get_airborne();
+        hurtbox.block_state = state.get_block_state(buffer.current_motion);
+        hurtbox.invulnerable = state.is_invulnerable();
+        hurtbox.strike_invulnerable = state.is_strike_invulnerable();
+        hurtbox.projectile_invulnerable = state.is_projectile_invulnerable();
+        hurtbox.throwable = state.is_throwable();
+        hurtbox.armored = state.is_armored();
+        hurtbox.counter_hit = state.get_counter_hit();
+      }
+    }
   }
 }
 
+/// Hide characters whose current state removes them from the screen
+pub fn set_character_visibility(
+  mut query: Query<(&CharacterState, &mut Visibility)>,
+) {
+  for (state, mut visibility) in query.iter_mut() {
+    visibility.is_visible = !state.is_vanished();
+  }
+}
+
+fn character_landing(state: &mut CharacterState, movement: &mut CharacterMovement) {
+  state.land(movement);
+  movement.land();
+}
@@ -18,11 +18,14 @@ use crate::{
   collision::{
     Hitbox,
     HitboxSerialized,
+    Pushbox,
+    PushboxSerialized
   },
   character::{
     CharacterMovement,
     CharacterMovementSerialized
-  }
+  },
+  meter::MeterGain
 };
 
 
@@ -34,7 +37,13 @@ pub struct CharacterSheetSerialized {
   pub hitboxes: Vec<HitboxSerialized>,
   pub attacks: Vec<AttackSerialized>,
   pub movement: CharacterMovementSerialized,
-  pub sprite_info: SpriteInfo
+  pub sprite_info: SpriteInfo,
+  #[serde(default)]
+  pub health: Option<u16>,
+  #[serde(default)]
+  pub meter_gain: MeterGain,
+  #[serde(default)]
+  pub pushbox: Option<PushboxSerialized>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -60,6 +69,9 @@ pub struct CharacterLibrary {
   hitboxes: HashMap<String, Hitbox>,
   attacks: HashMap<String, Attack>,
   movements: HashMap<String, CharacterMovement>,
+  healths: HashMap<String, u16>,
+  meter_gains: HashMap<String, MeterGain>,
+  pushboxes: HashMap<String, Pushbox>,
   atlases: HashMap<String, Handle<TextureAtlas>>,
 
 }
@@ -70,12 +82,18 @@ impl CharacterLibrary {
     let hitboxes: HashMap<String, Hitbox> = HashMap::new();
     let attacks: HashMap<String, Attack> = HashMap::new();
     let movements: HashMap<String, CharacterMovement> = HashMap::new();
+    let healths: HashMap<String, u16> = HashMap::new();
+    let meter_gains: HashMap<String, MeterGain> = HashMap::new();
+    let pushboxes: HashMap<String, Pushbox> = HashMap::new();
     let atlases: HashMap<String, Handle<TextureAtlas>> = HashMap::new();
     CharacterLibrary {
       animations,
       hitboxes,
       attacks,
       movements,
+      healths,
+      meter_gains,
+      pushboxes,
       atlases,
     }
   }
@@ -142,6 +160,16 @@ pub fn load_character_data(&mut self, character_name: &str, asset_server: &Res<A
       HashMap::from_iter::<HashMap<String, Attack>>(raw_attacks.iter().cloned().collect())
     );
 
+    if let Some(health) = character_sheet.health {
+      self.healths.insert(character_name.to_string(), health);
+    }
+
+    self.meter_gains.insert(character_name.to_string(), character_sheet.meter_gain);
+
+    if let Some(pushbox) = character_sheet.pushbox {
+      self.pushboxes.insert(character_name.to_string(), Pushbox::from_serialized(pushbox));
+    }
+
     let movement = CharacterMovement::from_serialized(
       character_sheet.movement
     );
@@ -189,6 +217,18 @@ pub fn load_character_data(&mut self, character_name: &str, asset_server: &Res<A
     }
   }
 
+  pub fn get_health(&self, health_id: &str) -> Option<u16> {
+    return self.healths.get(health_id).copied();
+  }
+
+  pub fn get_meter_gain(&self, meter_gain_id: &str) -> Option<MeterGain> {
+    return self.meter_gains.get(meter_gain_id).copied();
+  }
+
+  pub fn get_pushbox(&self, pushbox_id: &str) -> Option<Pushbox> {
+    return self.pushboxes.get(pushbox_id).copied();
+  }
+
   pub fn get_atlas(&self, atlas_id: &str) -> Option<Handle<TextureAtlas>> {
     if let Some(atlas) = self.atlases.get(atlas_id) {
       return Some(atlas.clone());
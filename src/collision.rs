@@ -3,8 +3,23 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::{
   character_library::CharacterLibrary,
-  character::PlayerId,
-  utils::countdown
+  animation::AnimationController,
+  attacks::Throw,
+  character::{
+    CharacterMovement,
+    PlayerId
+  },
+  utils::countdown,
+  constants::{
+    HITSTUN,
+    BLOCKSTUN,
+    HITSTUN_DECAY,
+    MIN_HITSTUN,
+    COUNTER_HIT_HITSTUN,
+    PUNISH_COUNTER_HITSTUN,
+    CHIP_DAMAGE_SCALING
+  },
+  stage::StageBounds
 };
 
 /// Box generated by attacks in game
@@ -33,6 +48,40 @@ pub struct Hitbox {
   active: bool,
   /// Is the hitbox attached to the player that generated it
   projectile: bool,
+  /// Does this hitbox bounce a juggled character off of the wall
+  wall_bounce: bool,
+  /// Does this hitbox bounce a juggled character off of the ground
+  ground_bounce: bool,
+  /// Does this hitbox cause a hard knockdown when the juggled character lands
+  hard_knockdown: bool,
+  /// Size of the hitbox, set when it is spawned
+  size: Vec2,
+  /// Hitboxes in the same group of an attack only hit each character once between them
+  hit_group: Option<u8>,
+  /// Frames before the hitbox can hit the same character again
+  rehit_interval: u8,
+  /// How many times the hitbox can hit each character
+  max_hits: u8,
+  /// Hits dealt to each character, indexed by player
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  victims: [HitRecord; 2],
+}
+
+/// Tracks how a hitbox has connected with a single character
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HitRecord {
+  /// Times the hitbox has hit the character
+  hits: u8,
+  /// Frames until the hitbox can hit the character again
+  cooldown: u8,
+}
+
+fn victim_index(player_id: &PlayerId) -> usize {
+  match player_id {
+    PlayerId::P1 => 0,
+    PlayerId::P2 => 1,
+  }
 }
 
 impl Hitbox {
@@ -48,14 +97,38 @@ impl Hitbox {
       duration: s.duration,
       chip: s.chip,
       projectile: s.projectile,
+      wall_bounce: s.wall_bounce,
+      ground_bounce: s.ground_bounce,
+      hard_knockdown: s.hard_knockdown,
       hit_state: HitState::None,
-      active: false
+      active: false,
+      size: Vec2::ZERO,
+      hit_group: None,
+      rehit_interval: 0,
+      max_hits: 1,
+      victims: [HitRecord::default(); 2],
+    }
+  }
+
+  /// Create an unblockable hitbox used to release a thrown character
+  pub fn from_throw(throw: &Throw) -> Self {
+    Hitbox {
+      attack_level: 4,
+      damage: throw.damage,
+      proration: 1.0,
+      force: throw.force,
+      hard_knockdown: true,
+      max_hits: 1,
+      ..Default::default()
     }
   }
 
-  /// if possible, lower the hitboxes duration by 1 frame
+  /// if possible, lower the hitboxes duration, and the time until it can hit each character again, by 1 frame
   pub fn tick(&mut self) {
     self.duration = countdown(self.duration);
+    for record in self.victims.iter_mut() {
+      record.cooldown = countdown(record.cooldown);
+    }
   }
 
   /// return if the hitbox should be removed
@@ -63,8 +136,139 @@ impl Hitbox {
     return self.duration == 0;
   }
 
+  /// Returns if the hitbox is still able to connect
+  pub fn can_hit(&self) -> bool {
+    match self.hit_state {
+      HitState::None => return true,
+      _ => return false
+    }
+  }
+
+  /// Allow the hitbox to connect again
+  pub fn reset_hit_state(&mut self) {
+    self.hit_state = HitState::None;
+  }
+
+  /// Returns if the hitbox is able to connect with the passed player
+  pub fn can_hit_player(&self, player_id: &PlayerId) -> bool {
+    let record = self.victims[victim_index(player_id)];
+    return record.hits < self.max_hits && record.cooldown == 0;
+  }
+
+  /// Returns if the hitbox has connected with the passed player
+  pub fn has_hit_player(&self, player_id: &PlayerId) -> bool {
+    return self.victims[victim_index(player_id)].hits > 0;
+  }
+
+  /// Record that the hitbox connected with the passed player
+  pub fn register_hit(&mut self, player_id: &PlayerId, blocked: bool) {
+    let record = &mut self.victims[victim_index(player_id)];
+    record.hits = record.hits.saturating_add(1);
+    record.cooldown = self.rehit_interval;
+    self.hit_state = if blocked {
+      HitState::Blocked
+    } else {
+      HitState::Hit
+    };
+  }
+
+  pub fn get_hit_group(&self) -> Option<u8> {
+    return self.hit_group;
+  }
+
+  pub fn get_size(&self) -> Vec2 {
+    return self.size;
+  }
+
+  /// Returns if the hitbox, placed at `position`, overlaps a hurtbox placed at `hurtbox_position`
+  pub fn overlaps(&self, position: Vec3, hurtbox: &Hurtbox, hurtbox_position: Vec3) -> bool {
+    return boxes_overlap(
+      position.truncate(),
+      self.size,
+      hurtbox_position.truncate() + hurtbox.offset,
+      hurtbox.size
+    );
+  }
+
+  /// Frames of hitstun caused by this hitbox, reduced by the number of hits already in the combo
+  pub fn get_hitstun(&self, combo_hits: u8) -> u8 {
+    let base = HITSTUN[(self.attack_level as usize).min(HITSTUN.len() - 1)];
+    return base.saturating_sub(combo_hits.saturating_mul(HITSTUN_DECAY)).max(MIN_HITSTUN);
+  }
+
+  /// Frames of blockstun caused by this hitbox
+  pub fn get_blockstun(&self) -> u8 {
+    return BLOCKSTUN[(self.attack_level as usize).min(BLOCKSTUN.len() - 1)];
+  }
+
+  pub fn get_force(&self) -> Vec2 {
+    return self.force;
+  }
+
+  pub fn get_damage(&self) -> u8 {
+    return self.damage;
+  }
+
+  /// Damage dealt when this hitbox is blocked, 0 if it doesn't cause chip damage
+  pub fn get_chip_damage(&self) -> u8 {
+    if !self.chip {
+      return 0;
+    }
+    return (self.damage as f32 * CHIP_DAMAGE_SCALING).round().max(1.0) as u8;
+  }
+
+  pub fn get_attack_level(&self) -> u8 {
+    return self.attack_level;
+  }
+
+  pub fn get_proration(&self) -> f32 {
+    return self.proration;
+  }
+
+  /// Returns if the hitbox sends grounded characters into a juggle
+  pub fn is_launcher(&self) -> bool {
+    return self.force.y > 0.0;
+  }
+
+  pub fn get_wall_bounce(&self) -> bool {
+    return self.wall_bounce;
+  }
+
+  pub fn get_ground_bounce(&self) -> bool {
+    return self.ground_bounce;
+  }
+
+  pub fn get_hard_knockdown(&self) -> bool {
+    return self.hard_knockdown;
+  }
+
+  pub fn get_duration(&self) -> u8 {
+    return self.duration;
+  }
+
+  pub fn is_projectile(&self) -> bool {
+    return self.projectile;
+  }
+
+  /// Returns if the hitbox ignores a hurtbox because of the character's invulnerability
+  pub fn is_ignored_by(&self, hurtbox: &Hurtbox) -> bool {
+    if hurtbox.invulnerable {
+      return true;
+    }
+    if self.projectile {
+      return hurtbox.projectile_invulnerable;
+    }
+    return hurtbox.strike_invulnerable;
+  }
+
   pub fn generate_collision(&self, hurtbox: &Hurtbox) -> Collision {
-    return Collision::new(self.clone(), self.is_blocked(hurtbox));
+    let blocked = self.is_blocked(hurtbox);
+    let mut collision = Collision::new(self.clone(), blocked);
+    if !blocked {
+      collision.armored = hurtbox.armored;
+      collision.counter_hit = hurtbox.counter_hit;
+    }
+    return collision;
   }
 
   /// Returns if a Hitbox is blocked by a Hurtbox it overlaps
@@ -102,7 +306,7 @@ impl Hitbox {
 
 
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct HitboxEvent {
   /// The data that the created hitbox should carry
   pub hitbox: Hitbox,
@@ -112,12 +316,83 @@ pub struct HitboxEvent {
   pub size: Vec2,
   /// what frame of the attack should the hitbox be generated
   pub frame: u8,
+  /// how the hitbox travels, if it is a projectile
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  pub projectile: Option<ProjectileData>,
+}
+
+/// How a projectile hitbox moves and how long it lasts
+#[derive(Debug, Clone, Default)]
+pub struct ProjectileData {
+  /// Velocity of the projectile, relative to the direction the character is facing
+  pub velocity: Vec2,
+  /// How many frames the projectile stays on screen
+  pub lifetime: u8,
+  /// How many hits the projectile can deal, or absorb from other projectiles, before it is destroyed
+  pub hits: u8,
+  /// Frames between each hit of a multi-hit projectile
+  pub hit_interval: u8,
+  /// Animation played by the projectile
+  pub animation: Option<String>,
+}
+
+impl ProjectileData {
+  /// Create projectile data from it's serialized counterpart
+  pub fn from_serialized(s: ProjectileSerialized) -> Self {
+    ProjectileData {
+      velocity: s.velocity.to_vec2(),
+      lifetime: s.lifetime,
+      hits: s.hits.max(1),
+      hit_interval: s.hit_interval.max(1),
+      animation: s.animation,
+    }
+  }
+}
+
+/// A hitbox that moves on it's own, seperate from the character that created it
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Projectile {
+  /// Distance the projectile travels each frame
+  pub velocity: Vec2,
+  /// Hits left before the projectile is destroyed
+  pub hits: u8,
+  /// Frames between each hit
+  pub hit_interval: u8,
+  /// Frames until the projectile can hit again
+  pub cooldown: u8,
+}
+
+impl Projectile {
+  pub fn new(data: &ProjectileData, facing_vector: f32) -> Self {
+    Projectile {
+      velocity: Vec2::new(data.velocity.x * facing_vector, data.velocity.y),
+      hits: data.hits,
+      hit_interval: data.hit_interval,
+      cooldown: 0,
+    }
+  }
+
+  /// Use up one of the projectile's hits, starting the wait until it can hit again
+  pub fn spend_hit(&mut self) {
+    self.hits = countdown(self.hits);
+    self.cooldown = self.hit_interval;
+  }
+
+  /// Returns if the projectile has no hits left
+  pub fn is_spent(&self) -> bool {
+    return self.hits == 0;
+  }
+
+  /// Use up every hit the projectile has left, so it's removed at the end of the frame
+  pub fn destroy(&mut self) {
+    self.hits = 0;
+  }
 }
 
 
 
 /// Serialized version of bevy's Vec2
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Default)]
 pub struct Vec2Serialzed {
   pub x: f32,
   pub y: f32,
@@ -133,12 +408,16 @@ impl Vec2Serialzed {
 impl HitboxEvent {
   /// Create a hitbox event from it's serialized counterpart
   pub fn from_serialized(s: HitboxEventSerialized, library: &CharacterLibrary, character_name: &str) -> Self {
-    let hitbox = library.get_hitbox(format!("{}_{}",character_name,s.hitbox.clone())).unwrap();
+    let mut hitbox = library.get_hitbox(format!("{}_{}",character_name,s.hitbox.clone())).unwrap();
+    hitbox.hit_group = s.hit_group;
+    hitbox.rehit_interval = s.rehit_interval;
+    hitbox.max_hits = s.max_hits.max(1);
     HitboxEvent {
       hitbox,
       position: s.position.to_vec2(),
       size: s.size.to_vec2(),
-      frame: s.frame
+      frame: s.frame,
+      projectile: s.projectile.map(ProjectileData::from_serialized),
     }
   }
 }
@@ -154,7 +433,7 @@ pub struct HurtboxEvent {
 }
 
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HitState {
   None,
   Hit,
@@ -204,6 +483,31 @@ pub struct HitboxEventSerialized {
   pub position: Vec2Serialzed,
   pub size: Vec2Serialzed,
   pub frame: u8,
+  #[serde(default)]
+  pub projectile: Option<ProjectileSerialized>,
+  #[serde(default)]
+  pub hit_group: Option<u8>,
+  #[serde(default)]
+  pub rehit_interval: u8,
+  #[serde(default = "default_max_hits")]
+  pub max_hits: u8,
+}
+
+fn default_max_hits() -> u8 {
+  1
+}
+
+/// Serialized version of a projectile
+#[derive(Deserialize, Serialize)]
+pub struct ProjectileSerialized {
+  pub velocity: Vec2Serialzed,
+  pub lifetime: u8,
+  #[serde(default)]
+  pub hits: u8,
+  #[serde(default)]
+  pub hit_interval: u8,
+  #[serde(default)]
+  pub animation: Option<String>,
 }
 
 /// Serialized version of a hitbox
@@ -219,67 +523,421 @@ pub struct HitboxSerialized {
   pub duration: u8,
   pub chip: bool,
   pub projectile: bool,
+  #[serde(default)]
+  pub wall_bounce: bool,
+  #[serde(default)]
+  pub ground_bounce: bool,
+  #[serde(default)]
+  pub hard_knockdown: bool,
 }
 
 
 /// Trait to implement a helper method on Commands to allow easily spawning hitboxes
 pub trait SpawnHitbox {
-  fn spawn_hitbox(&mut self, player_id: &PlayerId, hitbox_event: &HitboxEvent, parent_transform: &Transform, facing_vector: f32);
+  fn spawn_hitbox(&mut self, player_id: &PlayerId, owner: Entity, hitbox_event: &HitboxEvent, parent_transform: &Transform, facing_vector: f32);
+  fn spawn_throwbox(&mut self, player_id: &PlayerId, owner: Entity, throw: &Throw, parent_transform: &Transform, facing_vector: f32);
+  fn spawn_projectile(&mut self, player_id: &PlayerId, hitbox_event: &HitboxEvent, data: &ProjectileData, parent_transform: &Transform, facing_vector: f32, texture_atlas: Handle<TextureAtlas>, animation_controller: Option<AnimationController>);
 }
 
 impl SpawnHitbox for Commands<'_, '_>{
-  fn spawn_hitbox(&mut self, player_id: &PlayerId, hitbox_event: &HitboxEvent, parent_transform: &Transform, facing_vector: f32 ) {
+  fn spawn_hitbox(&mut self, player_id: &PlayerId, owner: Entity, hitbox_event: &HitboxEvent, parent_transform: &Transform, facing_vector: f32 ) {
     let offset = Vec3::new(hitbox_event.position.x * facing_vector, hitbox_event.position.y, 1.0);
     let parent_translation = parent_transform.translation;
     let transform = Transform::from_translation(parent_translation + offset);
+    let mut hitbox = hitbox_event.hitbox;
+    hitbox.size = hitbox_event.size;
 
-    self.spawn_bundle( SpriteBundle {
-      sprite: Sprite {
-        color: Color::rgb(0.25, 0.25, 0.75),
-        custom_size: Some(hitbox_event.size),
+    self.spawn_bundle((transform, GlobalTransform::default()))
+    .insert(player_id.clone())
+    .insert(HitboxOwner::new(owner, hitbox_event.position))
+    .insert(hitbox);
+  }
+
+  fn spawn_throwbox(&mut self, player_id: &PlayerId, owner: Entity, throw: &Throw, parent_transform: &Transform, facing_vector: f32) {
+    let offset = Vec3::new(throw.position.x * facing_vector, throw.position.y, 1.0);
+    let parent_translation = parent_transform.translation;
+    let transform = Transform::from_translation(parent_translation + offset);
+
+    self.spawn_bundle((transform, GlobalTransform::default()))
+    .insert(player_id.clone())
+    .insert(HitboxOwner::new(owner, throw.position))
+    .insert(Throwbox::new(*throw));
+  }
+
+  fn spawn_projectile(&mut self, player_id: &PlayerId, hitbox_event: &HitboxEvent, data: &ProjectileData, parent_transform: &Transform, facing_vector: f32, texture_atlas: Handle<TextureAtlas>, animation_controller: Option<AnimationController>) {
+    let offset = Vec3::new(hitbox_event.position.x * facing_vector, hitbox_event.position.y, 1.0);
+    let transform = Transform::from_translation(parent_transform.translation + offset);
+    let mut hitbox = hitbox_event.hitbox;
+    hitbox.size = hitbox_event.size;
+    hitbox.duration = data.lifetime;
+    hitbox.max_hits = data.hits;
+    hitbox.rehit_interval = data.hit_interval;
+
+    let mut projectile = self.spawn_bundle( SpriteSheetBundle {
+      sprite: TextureAtlasSprite {
+        flip_x: facing_vector < 0.0,
         ..Default::default()
       },
+      texture_atlas,
       transform,
       ..Default::default()
       }
-    )
-    .insert(player_id.clone())
-    .insert(hitbox_event.hitbox);
+    );
+    projectile
+      .insert(player_id.clone())
+      .insert(hitbox)
+      .insert(Projectile::new(data, facing_vector));
+    if let Some(controller) = animation_controller {
+      projectile.insert(controller);
+    }
+  }
+}
+
+/// Links a hitbox or throwbox to the character that spawned it, so it follows them until it expires
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HitboxOwner {
+  /// The character that spawned the box
+  pub entity: Entity,
+  /// Where the box is placed in relation to the character, before accounting for facing
+  pub offset: Vec2,
+}
+
+impl HitboxOwner {
+  pub fn new(entity: Entity, offset: Vec2) -> Self {
+    HitboxOwner {
+      entity,
+      offset
+    }
+  }
+}
+
+/// Keep hitboxes and throwboxes in place relative to the characters that spawned them
+#[doc(hidden)]
+pub fn follow_owners(
+  mut box_query: Query<(&HitboxOwner, &mut Transform), Without<CharacterMovement>>,
+  owner_query: Query<(&Transform, &CharacterMovement)>,
+) {
+  for (owner, mut transform) in box_query.iter_mut() {
+    if let Ok((owner_transform, movement)) = owner_query.get(owner.entity) {
+      let offset = Vec3::new(owner.offset.x * movement.facing_vector, owner.offset.y, 1.0);
+      transform.translation = owner_transform.translation + offset;
+    }
+  }
+}
+
+/// Box generated by throws, grabs the opponent regardless of how they are blocking
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Throwbox {
+  /// The throw performed when this box connects
+  pub throw: Throw,
+  /// How many frames will this throwbox stay out
+  duration: u8,
+}
+
+impl Throwbox {
+  pub fn new(throw: Throw) -> Self {
+    Throwbox {
+      throw,
+      duration: throw.active,
+    }
+  }
+
+  /// if possible, lower the throwboxes duration by 1 frame
+  pub fn tick(&mut self) {
+    self.duration = countdown(self.duration);
+  }
+
+  /// return if the throwbox should be removed
+  pub fn is_finished(&self) -> bool {
+    return self.duration == 0;
+  }
+
+  /// Returns if the throwbox, placed at `position`, overlaps a grabbable hurtbox placed at `hurtbox_position`
+  pub fn can_grab(&self, position: Vec3, hurtbox: &Hurtbox, hurtbox_position: Vec3) -> bool {
+    if !hurtbox.throwable || self.throw.air == hurtbox.is_grounded {
+      return false;
+    }
+    return boxes_overlap(
+      position.truncate(),
+      self.throw.size,
+      hurtbox_position.truncate() + hurtbox.offset,
+      hurtbox.size
+    );
   }
 }
 
+/// Returns if two boxes, described by their centers and sizes, overlap
+pub fn boxes_overlap(a_center: Vec2, a_size: Vec2, b_center: Vec2, b_size: Vec2) -> bool {
+  let distance = (a_center - b_center).abs();
+  let reach = (a_size + b_size) / 2.0;
+  return distance.x < reach.x && distance.y < reach.y;
+}
 
+/// Area of a character that can be struck by hitboxes
+#[derive(Component, Debug, Clone, Copy)]
 pub struct Hurtbox {
   pub is_grounded: bool,
   pub block_state: BlockState,
+  /// Is the character currently immune to hitboxes
+  pub invulnerable: bool,
+  /// Is the character currently immune to hitboxes attached to characters
+  pub strike_invulnerable: bool,
+  /// Is the character currently immune to projectiles
+  pub projectile_invulnerable: bool,
+  /// Can the character currently be grabbed by throwboxes
+  pub throwable: bool,
+  /// Will the character currently absorb a hit with armor
+  pub armored: bool,
+  /// Will a hit currently be a counter hit
+  pub counter_hit: Option<CounterHit>,
+  /// Where the hurtbox is placed in relation to the character
+  pub offset: Vec2,
+  /// Size of the hurtbox
+  pub size: Vec2,
+}
+
+impl Default for Hurtbox {
+  fn default() -> Self {
+    Hurtbox {
+      is_grounded: true,
+      block_state: BlockState::None,
+      invulnerable: false,
+      strike_invulnerable: false,
+      projectile_invulnerable: false,
+      throwable: true,
+      armored: false,
+      counter_hit: None,
+      offset: Vec2::new(0.0, -40.0),
+      size: Vec2::new(70.0, 170.0),
+    }
+  }
+}
+
+/// Space a character's body takes up
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Pushbox {
+  /// Where the pushbox is placed in relation to the character
+  pub offset: Vec2,
+  pub size: Vec2,
+}
+
+impl Default for Pushbox {
+  fn default() -> Self {
+    Pushbox {
+      offset: Vec2::new(0.0, -40.0),
+      size: Vec2::new(50.0, 150.0),
+    }
+  }
 }
 
+impl Pushbox {
+  /// Create a pushbox from it's serialized counterpart
+  pub fn from_serialized(s: PushboxSerialized) -> Self {
+    Pushbox {
+      offset: s.offset.to_vec2(),
+      size: s.size.to_vec2(),
+    }
+  }
+
+  /// Returns the center of the pushbox, for a character at `position` facing `facing_vector`
+  pub fn get_center(&self, position: Vec3, facing_vector: f32) -> Vec2 {
+    return position.truncate() + Vec2::new(self.offset.x * facing_vector, self.offset.y);
+  }
+
+  /// Returns how far two pushboxes overlap horizontally, or 0 if they aren't touching
+  pub fn get_overlap(&self, center: Vec2, other: &Pushbox, other_center: Vec2) -> f32 {
+    if !boxes_overlap(center, self.size, other_center, other.size) {
+      return 0.0;
+    }
+    return (self.size.x + other.size.x) / 2.0 - (center.x - other_center.x).abs();
+  }
+}
+
+/// Serialized version of a pushbox
+#[derive(Deserialize, Serialize)]
+pub struct PushboxSerialized {
+  pub offset: Vec2Serialzed,
+  pub size: Vec2Serialzed,
+}
+
+/// Returns the horizontal positions of two characters pushed `overlap` apart, each moving half the distance.
+/// A character stopped by a wall can't be pushed, so their opponent is pushed the rest of the way instead
+pub fn push_apart(a: f32, b: f32, overlap: f32, bounds: &StageBounds) -> (f32, f32) {
+  let direction = if b >= a { 1.0 } else { -1.0 };
+  let pushed_a = a - direction * overlap / 2.0;
+  let pushed_b = b + direction * overlap / 2.0;
+  let mut new_a = bounds.clamp(pushed_a);
+  let mut new_b = bounds.clamp(pushed_b);
+
+  let missing = (pushed_b - pushed_a - (new_b - new_a)) * direction;
+  if missing > 0.0 {
+    if new_a != pushed_a {
+      new_b = bounds.clamp(new_b + direction * missing);
+    } else {
+      new_a = bounds.clamp(new_a - direction * missing);
+    }
+  }
+  return (new_a, new_b);
+}
 
 pub struct Collision {
   pub hitbox: Hitbox,
   pub blocked: bool,
+  /// Was the hit absorbed by armor
+  pub armored: bool,
+  /// Did the hit land during the startup or recovery of an attack
+  pub counter_hit: Option<CounterHit>,
 }
 
 impl Collision {
   pub fn new(hitbox: Hitbox, blocked: bool) -> Self {
     Collision {
       hitbox,
-      blocked
+      blocked,
+      armored: false,
+      counter_hit: None,
     }
   }
 }
 
+/// Hits landed while the opponent is committed to an attack
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CounterHit {
+  /// Hit during the startup of an attack
+  Counter,
+  /// Hit during the recovery of an attack
+  Punish,
+}
+
+impl CounterHit {
+  /// Extra frames of hitstun caused by the counter hit
+  pub fn get_hitstun_bonus(&self) -> u8 {
+    match self {
+      CounterHit::Counter => COUNTER_HIT_HITSTUN,
+      CounterHit::Punish => PUNISH_COUNTER_HITSTUN,
+    }
+  }
+}
+
+/// Sent whenever a hit lands as a counter hit
+pub struct CounterHitEvent {
+  pub counter_hit: CounterHit,
+  pub player_id: PlayerId,
+  pub recieving_player_id: PlayerId,
+}
+
 pub struct CollisionEvent{
   pub collision: Collision,
   pub player_id: PlayerId,
   pub recieving_player_id: PlayerId,
 }
 
+pub struct ThrowEvent {
+  pub throw: Throw,
+  pub player_id: PlayerId,
+  pub recieving_player_id: PlayerId,
+}
 
+#[doc(hidden)]
+pub fn detect_throws(
+  mut coms: Commands,
+  throwbox_query: Query<(Entity, &PlayerId, &Throwbox, &Transform)>,
+  hurtbox_query: Query<(&PlayerId, &Hurtbox, &Transform)>,
+  mut throw_writer: EventWriter<ThrowEvent>,
+) {
+  for (entity, player_id, throwbox, throwbox_transform) in throwbox_query.iter() {
+    for (recieving_player_id, hurtbox, hurtbox_transform) in hurtbox_query.iter() {
+      if player_id == recieving_player_id {
+        continue;
+      }
+      if throwbox.can_grab(throwbox_transform.translation, hurtbox, hurtbox_transform.translation) {
+        throw_writer.send(
+          ThrowEvent {
+            throw: throwbox.throw,
+            player_id: *player_id,
+            recieving_player_id: *recieving_player_id,
+          }
+        );
+        coms.entity(entity).despawn_recursive();
+        break;
+      }
+    }
+  }
+}
 
+/// Hit groups of the current attack that have already connected with each character
+#[derive(Component, Debug, Clone, Default)]
+pub struct HitGroups {
+  hits: Vec<(u8, PlayerId)>,
+}
 
+impl HitGroups {
+  pub fn contains(&self, group: u8, player_id: &PlayerId) -> bool {
+    return self.hits.contains(&(group, *player_id));
+  }
 
-#[derive(PartialEq, Clone, Copy)]
+  pub fn insert(&mut self, group: u8, player_id: &PlayerId) {
+    if !self.contains(group, player_id) {
+      self.hits.push((group, *player_id));
+    }
+  }
+
+  /// Forget every hit, called when a new attack starts
+  pub fn clear(&mut self) {
+    self.hits.clear();
+  }
+}
+
+#[doc(hidden)]
+pub fn detect_collisions(
+  mut hitbox_query: Query<(&PlayerId, &mut Hitbox, &Transform, Option<&HitboxOwner>, Option<&Projectile>)>,
+  hurtbox_query: Query<(&PlayerId, &Hurtbox, &Transform)>,
+  mut group_query: Query<&mut HitGroups>,
+  mut collision_writer: EventWriter<CollisionEvent>,
+) {
+  for (player_id, mut hitbox, hitbox_transform, owner, projectile) in hitbox_query.iter_mut() {
+    // Destroyed projectiles stay around until the end of the frame, but can't hit anymore
+    if projectile.map_or(false, |p| p.is_spent()) {
+      continue;
+    }
+    for (recieving_player_id, hurtbox, hurtbox_transform) in hurtbox_query.iter() {
+      if player_id == recieving_player_id || !hitbox.can_hit_player(recieving_player_id) || hitbox.is_ignored_by(hurtbox) {
+        continue;
+      }
+      // Another hitbox in the same group has already hit this character
+      let group = hitbox.get_hit_group().zip(owner.map(|o| o.entity));
+      if let Some((group, owner_entity)) = group {
+        if let Ok(groups) = group_query.get_mut(owner_entity) {
+          if groups.contains(group, recieving_player_id) && !hitbox.has_hit_player(recieving_player_id) {
+            continue;
+          }
+        }
+      }
+      if hitbox.overlaps(hitbox_transform.translation, hurtbox, hurtbox_transform.translation) {
+        let collision = hitbox.generate_collision(hurtbox);
+        hitbox.register_hit(recieving_player_id, collision.blocked);
+        if let Some((group, owner_entity)) = group {
+          if let Ok(mut groups) = group_query.get_mut(owner_entity) {
+            groups.insert(group, recieving_player_id);
+          }
+        }
+        collision_writer.send(
+          CollisionEvent {
+            collision,
+            player_id: *player_id,
+            recieving_player_id: *recieving_player_id,
+          }
+        );
+      }
+    }
+  }
+}
+
+
+
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum BlockState {
   Stand {barrier: bool, instant: bool},
   Air {barrier: bool, instant: bool},
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use crate::{
+  character::CharacterMovement,
+  collision::{
+    Hitbox,
+    Hurtbox,
+    Pushbox,
+    Throwbox
+  },
+  utils::FighterSystemLabels
+};
+
+/// Draws hitboxes, hurtboxes, pushboxes and throwboxes on top of the game
+pub struct CollisionViewPlugin;
+
+impl Plugin for CollisionViewPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_resource::<CollisionView>()
+      .add_system(toggle_collision_view.label(FighterSystemLabels::CollisionViewToggle))
+      .add_system(attach_collision_views.label(FighterSystemLabels::CollisionViewCreation))
+      .add_system(
+        draw_collision_boxes
+          .after(FighterSystemLabels::CollisionViewToggle)
+          .after(FighterSystemLabels::CollisionViewCreation)
+      );
+  }
+}
+
+/// Controls whether collision boxes are drawn and how they look
+#[derive(Debug, Clone)]
+pub struct CollisionView {
+  pub enabled: bool,
+  /// Key that turns drawing on and off
+  pub toggle_key: Option<KeyCode>,
+  pub hitbox_color: Color,
+  pub hurtbox_color: Color,
+  pub pushbox_color: Color,
+  pub throwbox_color: Color,
+  /// Width of the solid border drawn around each box
+  pub outline_width: f32,
+}
+
+impl Default for CollisionView {
+  fn default() -> Self {
+    CollisionView {
+      enabled: false,
+      toggle_key: Some(KeyCode::F1),
+      hitbox_color: Color::rgba(0.9, 0.15, 0.15, 0.35),
+      hurtbox_color: Color::rgba(0.15, 0.4, 0.9, 0.35),
+      pushbox_color: Color::rgba(0.9, 0.9, 0.9, 0.25),
+      throwbox_color: Color::rgba(0.15, 0.85, 0.25, 0.35),
+      outline_width: 2.0,
+    }
+  }
+}
+
+/// Kinds of collision boxes the view can draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionBoxKind {
+  Hitbox,
+  Hurtbox,
+  Pushbox,
+  Throwbox,
+}
+
+/// Part of a drawn collision box, the translucent fill or one of the solid edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionBoxPart {
+  Fill,
+  Top,
+  Bottom,
+  Left,
+  Right,
+}
+
+const BOX_PARTS: [CollisionBoxPart; 5] = [
+  CollisionBoxPart::Fill,
+  CollisionBoxPart::Top,
+  CollisionBoxPart::Bottom,
+  CollisionBoxPart::Left,
+  CollisionBoxPart::Right,
+];
+
+impl CollisionBoxPart {
+  /// Returns the position and size of this part, for a box centered on `center`
+  pub fn get_layout(&self, center: Vec2, size: Vec2, outline_width: f32) -> (Vec2, Vec2) {
+    let half = size / 2.0;
+    match self {
+      CollisionBoxPart::Fill => (center, size),
+      CollisionBoxPart::Top => (center + Vec2::new(0.0, half.y), Vec2::new(size.x, outline_width)),
+      CollisionBoxPart::Bottom => (center - Vec2::new(0.0, half.y), Vec2::new(size.x, outline_width)),
+      CollisionBoxPart::Left => (center - Vec2::new(half.x, 0.0), Vec2::new(outline_width, size.y)),
+      CollisionBoxPart::Right => (center + Vec2::new(half.x, 0.0), Vec2::new(outline_width, size.y)),
+    }
+  }
+}
+
+/// Sprite drawing part of a collision box, spawned once as a child of the entity holding the box
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CollisionViewSprite {
+  pub kind: CollisionBoxKind,
+  pub part: CollisionBoxPart,
+}
+
+/// Trait to implement a helper method on Commands to allow easily attaching collision box sprites to an entity
+pub trait AttachCollisionView {
+  fn attach_collision_view(&mut self, entity: Entity, kind: CollisionBoxKind);
+}
+
+impl AttachCollisionView for Commands<'_, '_> {
+  fn attach_collision_view(&mut self, entity: Entity, kind: CollisionBoxKind) {
+    self.entity(entity).with_children(|parent| {
+      for part in BOX_PARTS {
+        parent.spawn_bundle(SpriteBundle {
+          visibility: Visibility {is_visible: false},
+          ..Default::default()
+        })
+        .insert(CollisionViewSprite {kind, part});
+      }
+    });
+  }
+}
+
+#[doc(hidden)]
+pub fn toggle_collision_view(
+  keyboard_input: Res<Input<KeyCode>>,
+  mut view: ResMut<CollisionView>,
+) {
+  if let Some(key) = view.toggle_key {
+    if keyboard_input.just_pressed(key) {
+      view.enabled = !view.enabled;
+    }
+  }
+}
+
+/// Give every new box the sprites used to draw it
+#[doc(hidden)]
+pub fn attach_collision_views(
+  mut coms: Commands,
+  hitbox_query: Query<Entity, Added<Hitbox>>,
+  throwbox_query: Query<Entity, Added<Throwbox>>,
+  hurtbox_query: Query<Entity, Added<Hurtbox>>,
+  pushbox_query: Query<Entity, Added<Pushbox>>,
+) {
+  for entity in hitbox_query.iter() {
+    coms.attach_collision_view(entity, CollisionBoxKind::Hitbox);
+  }
+  for entity in throwbox_query.iter() {
+    coms.attach_collision_view(entity, CollisionBoxKind::Throwbox);
+  }
+  for entity in hurtbox_query.iter() {
+    coms.attach_collision_view(entity, CollisionBoxKind::Hurtbox);
+  }
+  for entity in pushbox_query.iter() {
+    coms.attach_collision_view(entity, CollisionBoxKind::Pushbox);
+  }
+}
+
+/// Show or hide the collision box sprites, fitting them to the box they belong to
+#[doc(hidden)]
+pub fn draw_collision_boxes(
+  view: Res<CollisionView>,
+  mut sprite_query: Query<(&CollisionViewSprite, &Parent, &mut Sprite, &mut Transform, &mut Visibility)>,
+  hitbox_query: Query<&Hitbox>,
+  throwbox_query: Query<&Throwbox>,
+  hurtbox_query: Query<&Hurtbox>,
+  pushbox_query: Query<(&Pushbox, &CharacterMovement)>,
+) {
+  for (view_sprite, parent, mut sprite, mut transform, mut visibility) in sprite_query.iter_mut() {
+    visibility.is_visible = view.enabled;
+    if !view.enabled {
+      continue;
+    }
+
+    let collision_box = match view_sprite.kind {
+      CollisionBoxKind::Hitbox => hitbox_query.get(parent.0).ok().map(|h| (Vec2::ZERO, h.get_size(), view.hitbox_color, 903.0)),
+      CollisionBoxKind::Throwbox => throwbox_query.get(parent.0).ok().map(|t| (Vec2::ZERO, t.throw.size, view.throwbox_color, 902.0)),
+      CollisionBoxKind::Hurtbox => hurtbox_query.get(parent.0).ok().map(|h| (h.offset, h.size, view.hurtbox_color, 901.0)),
+      CollisionBoxKind::Pushbox => pushbox_query.get(parent.0).ok().map(|(p, m)| (Vec2::new(p.offset.x * m.facing_vector, p.offset.y), p.size, view.pushbox_color, 900.0)),
+    };
+
+    if let Some((offset, size, mut color, depth)) = collision_box {
+      if view_sprite.part != CollisionBoxPart::Fill {
+        color.set_a(1.0);
+      }
+      let (position, part_size) = view_sprite.part.get_layout(offset, size, view.outline_width);
+      sprite.color = color;
+      sprite.custom_size = Some(part_size);
+      transform.translation = Vec3::new(position.x, position.y, depth);
+    }
+  }
+}
@@ -0,0 +1,225 @@
+use bevy::prelude::*;
+use crate::{
+  character::{
+    CharacterState,
+    PlayerId
+  },
+  collision::CollisionEvent,
+  constants::{
+    COMBO_DAMAGE_SCALING,
+    HEALTH_RECOVERY_DELAY,
+    HEALTH_RECOVERY_RATE,
+    MIN_COMBO_SCALING,
+    RECOVERABLE_DAMAGE_SCALING
+  }
+};
+
+/// Remaining health of a character
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Health {
+  pub current: u16,
+  pub max: u16,
+  /// Part of the damage taken that comes back while the character isn't being hit
+  pub recoverable: u16,
+  /// Frames until recoverable health starts coming back
+  recovery_delay: u8,
+}
+
+impl Health {
+  pub fn new(max: u16) -> Self {
+    Health {
+      current: max,
+      max,
+      recoverable: 0,
+      recovery_delay: 0,
+    }
+  }
+
+  pub fn take_damage(&mut self, damage: u16) {
+    let taken = damage.min(self.current);
+    self.current -= taken;
+    self.recovery_delay = HEALTH_RECOVERY_DELAY;
+    if self.current == 0 {
+      // KO'd characters don't get anything back
+      self.recoverable = 0;
+      return;
+    }
+    let recoverable = (taken as f32 * RECOVERABLE_DAMAGE_SCALING).round() as u16;
+    self.recoverable = self.recoverable.saturating_add(recoverable).min(self.max - self.current);
+  }
+
+  /// Count down the recovery delay, then give back recoverable health a little every frame
+  pub fn recover(&mut self) {
+    if self.recovery_delay > 0 {
+      self.recovery_delay -= 1;
+      return;
+    }
+    let recovered = self.recoverable.min(HEALTH_RECOVERY_RATE);
+    self.recoverable -= recovered;
+    self.current += recovered;
+  }
+
+  pub fn is_empty(&self) -> bool {
+    return self.current == 0;
+  }
+
+  /// Returns the portion of health remaining, from 0 to 1
+  pub fn get_percentage(&self) -> f32 {
+    if self.max == 0 {
+      return 0.0;
+    }
+    return self.current as f32 / self.max as f32;
+  }
+
+  /// Returns the portion of health remaining once recoverable health comes back, from 0 to 1
+  pub fn get_recoverable_percentage(&self) -> f32 {
+    if self.max == 0 {
+      return 0.0;
+    }
+    return (self.current + self.recoverable) as f32 / self.max as f32;
+  }
+
+  /// Refill health, used between rounds
+  pub fn reset(&mut self) {
+    self.current = self.max;
+    self.recoverable = 0;
+    self.recovery_delay = 0;
+  }
+}
+
+/// Tracks the combo a character is currently stuck in
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct ComboTracker {
+  /// Hits landed in the current combo
+  hits: u8,
+  /// Total damage dealt by the current combo
+  damage: u16,
+  /// Proration of the hit that started the combo
+  proration: f32,
+  /// Player dealing the combo
+  #[cfg_attr(feature = "debug", inspectable(ignore))]
+  attacker: Option<PlayerId>,
+}
+
+impl ComboTracker {
+  /// Record a hit in the combo, returning the damage it deals after scaling
+  pub fn register_hit(&mut self, base_damage: u8, proration: f32) -> u16 {
+    if self.hits == 0 {
+      self.proration = proration;
+    }
+    let damage = (base_damage as f32 * self.get_scaling()).round().max(1.0) as u16;
+    self.hits = self.hits.saturating_add(1);
+    self.damage = self.damage.saturating_add(damage);
+    return damage;
+  }
+
+  /// Damage scaling applied to the next hit of the combo
+  pub fn get_scaling(&self) -> f32 {
+    if self.hits == 0 {
+      return 1.0;
+    }
+    let scaling = self.proration * COMBO_DAMAGE_SCALING.powi(self.hits as i32 - 1);
+    return scaling.max(MIN_COMBO_SCALING);
+  }
+
+  pub fn get_hits(&self) -> u8 {
+    return self.hits;
+  }
+
+  pub fn get_damage(&self) -> u16 {
+    return self.damage;
+  }
+
+  pub fn is_active(&self) -> bool {
+    return self.hits > 0;
+  }
+
+  /// End the combo
+  pub fn reset(&mut self) {
+    *self = ComboTracker::default();
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComboEventKind {
+  Started,
+  Extended,
+  Dropped,
+}
+
+/// Sent whenever a combo starts, gains a hit, or ends
+pub struct ComboEvent {
+  pub kind: ComboEventKind,
+  pub player_id: PlayerId,
+  pub recieving_player_id: PlayerId,
+  pub hits: u8,
+  pub damage: u16,
+}
+
+/// Count hits and apply scaled damage to characters who get hit, and chip damage to characters who block
+#[doc(hidden)]
+pub fn update_combos(
+  mut collision_reader: EventReader<CollisionEvent>,
+  mut query: Query<(&PlayerId, &CharacterState, &mut ComboTracker, &mut Health)>,
+  mut combo_writer: EventWriter<ComboEvent>,
+) {
+  for event in collision_reader.iter() {
+    if event.collision.armored {
+      continue;
+    }
+    for (player_id, _, mut tracker, mut health) in query.iter_mut() {
+      if *player_id != event.recieving_player_id {
+        continue;
+      }
+      let hitbox = &event.collision.hitbox;
+      if event.collision.blocked {
+        // Blocked hits don't start or extend a combo
+        health.take_damage(hitbox.get_chip_damage() as u16);
+        continue;
+      }
+      let kind = if tracker.is_active() {
+        ComboEventKind::Extended
+      } else {
+        ComboEventKind::Started
+      };
+      let damage = tracker.register_hit(hitbox.get_damage(), hitbox.get_proration());
+      tracker.attacker = Some(event.player_id);
+      health.take_damage(damage);
+      combo_writer.send(ComboEvent {
+        kind,
+        player_id: event.player_id,
+        recieving_player_id: *player_id,
+        hits: tracker.get_hits(),
+        damage: tracker.get_damage(),
+      });
+    }
+  }
+
+  for (player_id, state, mut tracker, _) in query.iter_mut() {
+    if tracker.is_active() && !state.is_in_combo() {
+      combo_writer.send(ComboEvent {
+        kind: ComboEventKind::Dropped,
+        player_id: tracker.attacker.unwrap_or(player_id.opponent()),
+        recieving_player_id: *player_id,
+        hits: tracker.get_hits(),
+        damage: tracker.get_damage(),
+      });
+      tracker.reset();
+    }
+  }
+}
+
+/// Give back recoverable health to characters who aren't being hit or blocking
+#[doc(hidden)]
+pub fn recover_health(
+  mut query: Query<(&CharacterState, &mut Health)>,
+) {
+  for (state, mut health) in query.iter_mut() {
+    if state.is_interrupted() || state.is_in_combo() || health.is_empty() {
+      continue;
+    }
+    health.recover();
+  }
+}
@@ -1,5 +1,6 @@
 use regex::Regex;
 use crate::{
+  camera::CameraEffect,
   inputs::{
     CommandMotion,
     CommandType
@@ -7,7 +8,7 @@ use crate::{
 };
 
 lazy_static! {
-  pub static ref MOTIONS: [CommandMotion; 8] = [
+  pub static ref MOTIONS: [CommandMotion; 9] = [
     CommandMotion::new(
       1,
       Regex::new("([^69]+[69]{1,5}[^6]{0,9}5[^5]{0,4}6)").unwrap(),
@@ -48,5 +49,80 @@ lazy_static! {
       Regex::new("(4[^4]{0,6}2[^2]{0,6}6)").unwrap(),
       CommandType::HALF_CIRCLE_FORWARD
     ),
+    CommandMotion::new(
+      1,
+      // Anchored to the end of the buffer, so only the current frame's up input can complete a super jump
+      Regex::new("([123][^123]{0,6}[789])$").unwrap(),
+      CommandType::SUPER_JUMP
+    ),
   ];
 }
+
+/// Frames of hitstun caused by a hitbox, indexed by attack level
+pub const HITSTUN: [u8; 5] = [11, 13, 15, 17, 19];
+/// Frames of blockstun caused by a hitbox, indexed by attack level
+pub const BLOCKSTUN: [u8; 5] = [9, 11, 13, 15, 17];
+/// Frames of hitstun removed for every hit already in a combo
+pub const HITSTUN_DECAY: u8 = 1;
+/// Hitstun can never be scaled below this many frames
+pub const MIN_HITSTUN: u8 = 4;
+/// Extra gravity applied to a juggled character for every hit in the combo
+pub const JUGGLE_GRAVITY_SCALING: f32 = 0.05;
+/// Portion of velocity kept when bouncing off of a wall or the ground
+pub const BOUNCE_RESTITUTION: f32 = 0.6;
+/// Distance from the center of the stage to either wall, when no stage has been spawned
+pub const WALL_POSITION: f32 = 640.0;
+/// Fewest frames a back leap spends in the air, shorter leaps would land before leaving the ground
+pub const MIN_BACKLEAP_AIRTIME: u8 = 4;
+/// How long both characters are stuck after a throw is broken
+pub const THROW_TECH_DURATION: u8 = 20;
+/// Speed both characters are pushed apart with after a throw is broken
+pub const THROW_TECH_PUSHBACK: f32 = 8.0;
+/// Damage multiplier applied for every hit after the first in a combo, on top of the first hit's proration
+pub const COMBO_DAMAGE_SCALING: f32 = 0.9;
+/// Combo damage can never be scaled below this multiplier
+pub const MIN_COMBO_SCALING: f32 = 0.2;
+/// Portion of a hitbox's damage dealt when it's blocked, if it causes chip damage
+pub const CHIP_DAMAGE_SCALING: f32 = 0.25;
+/// Health characters are given when their sheet doesn't set it
+pub const DEFAULT_HEALTH: u16 = 1000;
+/// Portion of damage taken that can be recovered while the character isn't being hit
+pub const RECOVERABLE_DAMAGE_SCALING: f32 = 0.5;
+/// Frames after taking damage before recoverable health starts coming back
+pub const HEALTH_RECOVERY_DELAY: u8 = 60;
+/// Recoverable health given back every frame once it starts coming back
+pub const HEALTH_RECOVERY_RATE: u16 = 1;
+/// Frames in a round, 99 seconds at 60 frames per second
+pub const ROUND_TIME: u16 = 99 * 60;
+/// Frames before players can act at the start of a round
+pub const INTRO_DURATION: u16 = 90;
+/// Frames between a KO or time over and the end of the round
+pub const KO_DURATION: u16 = 120;
+/// Frames between the end of a round and the start of the next
+pub const ROUND_END_DURATION: u16 = 60;
+/// Rounds in a match, the first player to win the majority of them wins
+pub const DEFAULT_BEST_OF: u8 = 3;
+/// Distance from the center of the stage each character starts a round at
+pub const STARTING_POSITION: f32 = 40.0;
+/// Portion of the way the camera zooms towards it's target scale every frame
+pub const CAMERA_FOLLOW_SPEED: f32 = 0.05;
+/// Hits of this attack level or higher shake the camera
+pub const HEAVY_HIT_LEVEL: u8 = 3;
+/// Shake played when a heavy hit connects
+pub const HEAVY_HIT_SHAKE: CameraEffect = CameraEffect::Shake {amplitude: 6.0, frequency: 20.0, decay: 0.8, duration: 12};
+/// Shake played when a character is KO'd
+pub const KO_SHAKE: CameraEffect = CameraEffect::Shake {amplitude: 12.0, frequency: 15.0, decay: 0.9, duration: 40};
+/// Camera scale used to zoom in on a character during their superflash
+pub const SUPERFLASH_ZOOM: f32 = 0.35;
+/// Meter in a single bar, super moves usually cost one or more bars
+pub const METER_BAR: u16 = 100;
+/// Most meter a character can hold
+pub const MAX_METER: u16 = METER_BAR * 3;
+/// Frames gameplay is frozen for when a super starts, unless the attack sets it's own
+pub const SUPERFLASH_DURATION: u8 = 30;
+/// How many projectiles each player can have on screen at once
+pub const PROJECTILE_LIMIT: usize = 1;
+/// Extra frames of hitstun caused by hitting a character during the startup of their attack
+pub const COUNTER_HIT_HITSTUN: u8 = 4;
+/// Extra frames of hitstun caused by hitting a character during the recovery of their attack
+pub const PUNISH_COUNTER_HITSTUN: u8 = 8;
@@ -1,119 +1,197 @@
-use bevy::prelude::*;
+use bevy::{
+  diagnostic::{
+    Diagnostics,
+    FrameTimeDiagnosticsPlugin
+  },
+  prelude::*
+};
 use crate::{
-    character::{
-        PlayerId
-    }
+  character::{
+    CharacterMovement,
+    CharacterState,
+    PlayerId
+  },
+  utils::FighterSystemLabels
 };
 
+/// Lists the state, physics and air options of each character on screen
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_resource::<DebugOverlayConfig>()
+      .add_startup_system(spawn_debug_overlay.after(FighterSystemLabels::Setup))
+      .add_system(update_debug_overlay);
+  }
+}
+
+/// Pieces of information the debug overlay can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugField {
+  /// Current state, along with it's timers
+  State,
+  Velocity,
+  Facing,
+  /// Air jumps and airdashes remaining
+  AirOptions,
+  /// Interpolated force currently moving the character
+  Forces,
+  Fps,
+}
+
+/// Controls what the debug overlay shows, insert your own before adding [DebugOverlayPlugin] to change it
+#[derive(Debug, Clone)]
+pub struct DebugOverlayConfig {
+  /// Fields shown for each player, in order
+  pub fields: Vec<DebugField>,
+  /// Path to the font, relative to the assets folder
+  pub font: String,
+  pub font_size: f32,
+  pub color: Color,
+  pub visible: bool,
+}
+
+impl Default for DebugOverlayConfig {
+  fn default() -> Self {
+    DebugOverlayConfig {
+      fields: vec![
+        DebugField::State,
+        DebugField::Velocity,
+        DebugField::Facing,
+        DebugField::AirOptions,
+        DebugField::Forces,
+        DebugField::Fps,
+      ],
+      font: "fonts/Roboto-Black.ttf".to_string(),
+      font_size: 20.0,
+      color: Color::BLACK,
+      visible: true,
+    }
+  }
+}
+
+/// Text showing a single player's debug info
+#[derive(Component)]
+pub struct DebugOverlayText;
+
 pub trait SpawnDebugUi {
-  fn spawn_debug_ui(&mut self, player_id: PlayerId,  font: &Handle<Font>);
+  fn spawn_debug_ui(&mut self, player_id: PlayerId, font: &Handle<Font>, config: &DebugOverlayConfig);
 }
 
 impl SpawnDebugUi for Commands<'_,'_> {
-  fn spawn_debug_ui(&mut self, player_id: PlayerId, font: &Handle<Font>) {
-    let flex = match player_id {
-      PlayerId::P1 => AlignSelf::FlexStart,
-      PlayerId::P2 => AlignSelf::FlexEnd
+  fn spawn_debug_ui(&mut self, player_id: PlayerId, font: &Handle<Font>, config: &DebugOverlayConfig) {
+    let (position, horizontal) = match player_id {
+      PlayerId::P1 => (Rect {left: Val::Px(10.0), top: Val::Px(80.0), ..Default::default()}, HorizontalAlign::Left),
+      PlayerId::P2 => (Rect {right: Val::Px(10.0), top: Val::Px(80.0), ..Default::default()}, HorizontalAlign::Right),
     };
-      let font_size = 20.0;
-      self
-        .spawn_bundle(TextBundle {
-            style: Style {
-                align_self: flex,
-                align_items: AlignItems::FlexStart,
-                flex_direction: FlexDirection::Row,
-                ..Default::default()
-            },
-            text: Text {
-                sections: vec![
-                    TextSection {
-                        value: "Action State: ".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                    TextSection {
-                        value: "Busy: ".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                    TextSection {
-                        value: "Is Grounded: ".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                    TextSection {
-                        value: "Air Dashes: ".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                    TextSection {
-                        value: "Airdash Lockout: ".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                    TextSection {
-                        value: "Velocity: ".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                    TextSection {
-                        value: "Airdash Time: ".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                    TextSection {
-                        value: "Facing Vector: ".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                    TextSection {
-                        value: "FPS: ".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                    TextSection {
-                        value: "".to_string(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size,
-                            color: Color::BLACK,
-                        },
-                    },
-                ],
-                alignment: TextAlignment {
-                  vertical: VerticalAlign::Top,
-                  horizontal: HorizontalAlign::Left,
-                }
-            },
-            ..Default::default()
-        })
-        .insert(player_id);
+    self
+      .spawn_bundle(TextBundle {
+        style: Style {
+          position_type: PositionType::Absolute,
+          position,
+          ..Default::default()
+        },
+        text: Text::with_section(
+          "",
+          TextStyle {
+            font: font.clone(),
+            font_size: config.font_size,
+            color: config.color,
+          },
+          TextAlignment {
+            vertical: VerticalAlign::Top,
+            horizontal,
+          }
+        ),
+        ..Default::default()
+      })
+      .insert(player_id)
+      .insert(DebugOverlayText);
+  }
+}
+
+/// Returns a short description of a state and it's timers
+pub fn describe_state(state: &CharacterState) -> String {
+  use CharacterState::*;
+  match state {
+    Attacking {duration, attack, cancellable, hit_state}
+    | AttackingAirborne {duration, attack, cancellable, hit_state} => format!(
+      "Attacking: {} (frame {}/{}, remaining {}, cancellable {}, {:?})",
+      attack.name,
+      attack.current_frame(*duration),
+      attack.busy,
+      duration,
+      cancellable,
+      hit_state
+    ),
+    Throwing {duration, throw:_} => format!("Throwing {{ duration: {} }}", duration),
+    _ => format!("{:?}", state)
+  }
+}
+
+/// Returns the line shown for a single field
+pub fn describe_field(field: DebugField, state: &CharacterState, movement: &CharacterMovement, fps: f64) -> String {
+  match field {
+    DebugField::State => describe_state(state),
+    DebugField::Velocity => format!("Velocity: ({:.2}, {:.2})", movement.velocity.x, movement.velocity.y),
+    DebugField::Facing => format!("Facing Vector: {}", movement.facing_vector),
+    DebugField::AirOptions => format!(
+      "Air Jumps: {}/{} Airdashes: {}/{}",
+      movement.air_jumps_remaining,
+      movement.air_jumps,
+      movement.airdashes_remaining,
+      movement.airdashes
+    ),
+    DebugField::Forces => match movement.interpolated_force {
+      Some(force) => format!(
+        "Force: ({:.2}, {:.2}) for {} frames",
+        force.get_current_velocity().x,
+        force.get_current_velocity().y,
+        force.get_frames_remaining()
+      ),
+      None => "Force: None".to_string()
+    },
+    DebugField::Fps => format!("FPS: {:.1}", fps),
+  }
+}
+
+#[doc(hidden)]
+pub fn spawn_debug_overlay(
+  mut coms: Commands,
+  config: Res<DebugOverlayConfig>,
+  asset_server: Res<AssetServer>,
+) {
+  let font: Handle<Font> = asset_server.load(&config.font[..]);
+  coms.spawn_debug_ui(PlayerId::P1, &font, &config);
+  coms.spawn_debug_ui(PlayerId::P2, &font, &config);
+}
+
+#[doc(hidden)]
+pub fn update_debug_overlay(
+  config: Res<DebugOverlayConfig>,
+  diagnostics: Res<Diagnostics>,
+  character_query: Query<(&PlayerId, &CharacterState, &CharacterMovement)>,
+  mut text_query: Query<(&PlayerId, &mut Text, &mut Visibility), With<DebugOverlayText>>,
+) {
+  let fps = diagnostics
+    .get(FrameTimeDiagnosticsPlugin::FPS)
+    .and_then(|fps| fps.average())
+    .unwrap_or(0.0);
+
+  for (text_player_id, mut text, mut visibility) in text_query.iter_mut() {
+    visibility.is_visible = config.visible;
+    if !config.visible {
+      continue;
+    }
+    for (player_id, state, movement) in character_query.iter() {
+      if player_id == text_player_id {
+        text.sections[0].value = config.fields
+          .iter()
+          .map(|field| describe_field(*field, state, movement, fps))
+          .collect::<Vec<String>>()
+          .join("\n");
+      }
+    }
   }
 }
@@ -0,0 +1,420 @@
+use bevy::prelude::*;
+use crate::{
+  character::PlayerId,
+  collision::{
+    CounterHit,
+    CounterHitEvent
+  },
+  combo::{
+    ComboEvent,
+    ComboEventKind,
+    Health
+  },
+  constants::METER_BAR,
+  meter::SuperMeter,
+  round::MatchState,
+  utils::FighterSystemLabels
+};
+
+/// Draws health, meter, the round timer, round wins, combos and counter hit callouts
+pub struct FighterHudPlugin;
+
+impl Plugin for FighterHudPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .init_resource::<HudStyle>()
+      .add_startup_system(spawn_hud.after(FighterSystemLabels::Setup))
+      .add_system(update_health_bars)
+      .add_system(update_meter_bars)
+      .add_system(update_round_timer)
+      .add_system(update_round_wins)
+      .add_system(update_combo_counters)
+      .add_system(update_callouts);
+  }
+}
+
+/// Colors, sizes and timings used by the HUD, insert your own before adding [FighterHudPlugin] to restyle it
+#[derive(Debug, Clone)]
+pub struct HudStyle {
+  /// Path to the font, relative to the assets folder
+  pub font: String,
+  pub font_size: f32,
+  pub text_color: Color,
+  pub bar_background_color: Color,
+  pub health_color: Color,
+  /// Color of health that comes back while the character isn't being hit
+  pub recoverable_color: Color,
+  /// Color of damage that hasn't drained out of the health bar yet
+  pub damage_color: Color,
+  pub meter_color: Color,
+  pub counter_color: Color,
+  pub punish_color: Color,
+  /// Width of the health and meter bars, as a percentage of the screen
+  pub bar_width: f32,
+  pub health_height: f32,
+  pub meter_height: f32,
+  /// Seconds before damage starts draining out of the health bar
+  pub damage_drain_delay: f32,
+  /// Portion of the health bar drained every second
+  pub damage_drain_speed: f32,
+  /// Seconds a combo counter stays up after the combo is dropped
+  pub combo_linger: f32,
+  /// Seconds a counter hit callout stays up
+  pub callout_duration: f32,
+}
+
+impl Default for HudStyle {
+  fn default() -> Self {
+    HudStyle {
+      font: "fonts/Roboto-Black.ttf".to_string(),
+      font_size: 24.0,
+      text_color: Color::WHITE,
+      bar_background_color: Color::rgb(0.1, 0.1, 0.1),
+      health_color: Color::rgb(0.95, 0.8, 0.1),
+      recoverable_color: Color::rgb(0.85, 0.1, 0.1),
+      damage_color: Color::rgb(0.9, 0.9, 0.9),
+      meter_color: Color::rgb(0.2, 0.5, 0.95),
+      counter_color: Color::rgb(0.95, 0.4, 0.1),
+      punish_color: Color::rgb(0.9, 0.1, 0.5),
+      bar_width: 40.0,
+      health_height: 24.0,
+      meter_height: 12.0,
+      damage_drain_delay: 0.75,
+      damage_drain_speed: 0.5,
+      combo_linger: 1.0,
+      callout_duration: 1.0,
+    }
+  }
+}
+
+/// Player a HUD element shows the state of
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HudOwner(pub PlayerId);
+
+/// Portion of a player's health bar that is filled
+#[derive(Component)]
+pub struct HealthBar;
+
+/// Recoverable health, shown in red behind the health bar
+#[derive(Component)]
+pub struct RecoverableBar;
+
+/// Recent damage, shown behind the health bar until it drains away
+#[derive(Component, Default)]
+pub struct DamageBar {
+  /// Health the bar is currently showing, from 0 to 1
+  percentage: f32,
+  /// Seconds until the bar starts draining
+  delay: f32,
+}
+
+#[derive(Component)]
+pub struct MeterBar;
+
+/// Number of full meter bars a player has
+#[derive(Component)]
+pub struct MeterText;
+
+#[derive(Component)]
+pub struct RoundTimerText;
+
+#[derive(Component)]
+pub struct RoundWinText;
+
+#[derive(Component, Default)]
+pub struct ComboText {
+  /// Seconds until the counter is hidden, once the combo is dropped
+  linger: Option<f32>,
+}
+
+#[derive(Component, Default)]
+pub struct CalloutText {
+  /// Seconds until the callout is hidden
+  timer: f32,
+}
+
+fn text_bundle(value: &str, style: &HudStyle, font: &Handle<Font>, position: Rect<Val>) -> TextBundle {
+  TextBundle {
+    style: Style {
+      position_type: PositionType::Absolute,
+      position,
+      ..Default::default()
+    },
+    text: Text::with_section(
+      value,
+      TextStyle {
+        font: font.clone(),
+        font_size: style.font_size,
+        color: style.text_color,
+      },
+      Default::default()
+    ),
+    ..Default::default()
+  }
+}
+
+/// Anchor a node to the left side of the screen for player 1, or the right side for player 2
+fn side_position(player_id: PlayerId, horizontal: f32, top: Option<f32>, bottom: Option<f32>) -> Rect<Val> {
+  let mut position = Rect::<Val> {
+    top: top.map(Val::Px).unwrap_or(Val::Undefined),
+    bottom: bottom.map(Val::Px).unwrap_or(Val::Undefined),
+    ..Default::default()
+  };
+  match player_id {
+    PlayerId::P1 => position.left = Val::Percent(horizontal),
+    PlayerId::P2 => position.right = Val::Percent(horizontal),
+  }
+  return position;
+}
+
+fn spawn_bar<T: Component>(parent: &mut ChildBuilder, player_id: PlayerId, color: Color, marker: T) {
+  let position = match player_id {
+    PlayerId::P1 => Rect {left: Val::Px(0.0), top: Val::Px(0.0), ..Default::default()},
+    PlayerId::P2 => Rect {right: Val::Px(0.0), top: Val::Px(0.0), ..Default::default()},
+  };
+  parent
+    .spawn_bundle(NodeBundle {
+      style: Style {
+        position_type: PositionType::Absolute,
+        position,
+        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+        ..Default::default()
+      },
+      color: UiColor(color),
+      ..Default::default()
+    })
+    .insert(HudOwner(player_id))
+    .insert(marker);
+}
+
+#[doc(hidden)]
+pub fn spawn_hud(
+  mut coms: Commands,
+  style: Res<HudStyle>,
+  asset_server: Res<AssetServer>,
+) {
+  let font: Handle<Font> = asset_server.load(&style.font[..]);
+  coms
+    .spawn_bundle(NodeBundle {
+      style: Style {
+        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+        position_type: PositionType::Absolute,
+        ..Default::default()
+      },
+      color: UiColor(Color::NONE),
+      ..Default::default()
+    })
+    .with_children(|root| {
+      for player_id in [PlayerId::P1, PlayerId::P2] {
+        root
+          .spawn_bundle(NodeBundle {
+            style: Style {
+              position_type: PositionType::Absolute,
+              position: side_position(player_id, 2.0, Some(16.0), None),
+              size: Size::new(Val::Percent(style.bar_width), Val::Px(style.health_height)),
+              ..Default::default()
+            },
+            color: UiColor(style.bar_background_color),
+            ..Default::default()
+          })
+          .with_children(|bar| {
+            spawn_bar(bar, player_id, style.damage_color, DamageBar {percentage: 1.0, delay: 0.0});
+            spawn_bar(bar, player_id, style.recoverable_color, RecoverableBar);
+            spawn_bar(bar, player_id, style.health_color, HealthBar);
+          });
+
+        root
+          .spawn_bundle(NodeBundle {
+            style: Style {
+              position_type: PositionType::Absolute,
+              position: side_position(player_id, 2.0, None, Some(16.0)),
+              size: Size::new(Val::Percent(style.bar_width / 2.0), Val::Px(style.meter_height)),
+              ..Default::default()
+            },
+            color: UiColor(style.bar_background_color),
+            ..Default::default()
+          })
+          .with_children(|bar| {
+            spawn_bar(bar, player_id, style.meter_color, MeterBar);
+          });
+
+        root
+          .spawn_bundle(text_bundle("0", &style, &font, side_position(player_id, 2.0, None, Some(16.0 + style.meter_height))))
+          .insert(HudOwner(player_id))
+          .insert(MeterText);
+
+        root
+          .spawn_bundle(text_bundle("", &style, &font, side_position(player_id, 2.0, Some(20.0 + style.health_height), None)))
+          .insert(HudOwner(player_id))
+          .insert(RoundWinText);
+
+        root
+          .spawn_bundle(text_bundle("", &style, &font, side_position(player_id, 4.0, Some(160.0), None)))
+          .insert(HudOwner(player_id))
+          .insert(ComboText::default());
+
+        root
+          .spawn_bundle(text_bundle("", &style, &font, side_position(player_id, 4.0, Some(120.0), None)))
+          .insert(HudOwner(player_id))
+          .insert(CalloutText::default());
+      }
+
+      root
+        .spawn_bundle(text_bundle("", &style, &font, Rect {left: Val::Percent(48.0), top: Val::Px(12.0), ..Default::default()}))
+        .insert(RoundTimerText);
+    });
+}
+
+#[doc(hidden)]
+pub fn update_health_bars(
+  time: Res<Time>,
+  style: Res<HudStyle>,
+  character_query: Query<(&PlayerId, &Health)>,
+  mut health_query: Query<(&HudOwner, &mut Style), (With<HealthBar>, Without<RecoverableBar>, Without<DamageBar>)>,
+  mut recoverable_query: Query<(&HudOwner, &mut Style), (With<RecoverableBar>, Without<HealthBar>, Without<DamageBar>)>,
+  mut damage_query: Query<(&HudOwner, &mut Style, &mut DamageBar), (Without<HealthBar>, Without<RecoverableBar>)>,
+) {
+  for (player_id, health) in character_query.iter() {
+    let percentage = health.get_percentage();
+    for (owner, mut bar_style) in health_query.iter_mut() {
+      if owner.0 == *player_id {
+        bar_style.size.width = Val::Percent(percentage * 100.0);
+      }
+    }
+
+    let recoverable_percentage = health.get_recoverable_percentage();
+    for (owner, mut bar_style) in recoverable_query.iter_mut() {
+      if owner.0 == *player_id {
+        bar_style.size.width = Val::Percent(recoverable_percentage * 100.0);
+      }
+    }
+
+    // Damage drains down to the recoverable health, not past it
+    for (owner, mut bar_style, mut damage_bar) in damage_query.iter_mut() {
+      if owner.0 != *player_id {
+        continue;
+      }
+      if damage_bar.percentage < recoverable_percentage {
+        // Health was refilled, between rounds or by recovering
+        damage_bar.percentage = recoverable_percentage;
+      } else if damage_bar.percentage > recoverable_percentage {
+        if damage_bar.delay > 0.0 {
+          damage_bar.delay -= time.delta_seconds();
+        } else {
+          damage_bar.percentage = (damage_bar.percentage - style.damage_drain_speed * time.delta_seconds()).max(recoverable_percentage);
+        }
+      } else {
+        damage_bar.delay = style.damage_drain_delay;
+      }
+      bar_style.size.width = Val::Percent(damage_bar.percentage * 100.0);
+    }
+  }
+}
+
+#[doc(hidden)]
+pub fn update_meter_bars(
+  character_query: Query<(&PlayerId, &SuperMeter)>,
+  mut bar_query: Query<(&HudOwner, &mut Style), With<MeterBar>>,
+  mut text_query: Query<(&HudOwner, &mut Text), With<MeterText>>,
+) {
+  for (player_id, meter) in character_query.iter() {
+    for (owner, mut bar_style) in bar_query.iter_mut() {
+      if owner.0 == *player_id {
+        bar_style.size.width = Val::Percent(meter.get_percentage() * 100.0);
+      }
+    }
+    for (owner, mut text) in text_query.iter_mut() {
+      if owner.0 == *player_id {
+        text.sections[0].value = format!("{}", meter.get_current() / METER_BAR);
+      }
+    }
+  }
+}
+
+#[doc(hidden)]
+pub fn update_round_timer(
+  match_state: Res<MatchState>,
+  mut query: Query<&mut Text, With<RoundTimerText>>,
+) {
+  for mut text in query.iter_mut() {
+    text.sections[0].value = format!("{}", match_state.get_seconds_remaining());
+  }
+}
+
+#[doc(hidden)]
+pub fn update_round_wins(
+  match_state: Res<MatchState>,
+  mut query: Query<(&HudOwner, &mut Text), With<RoundWinText>>,
+) {
+  for (owner, mut text) in query.iter_mut() {
+    text.sections[0].value = "* ".repeat(match_state.get_wins(&owner.0) as usize);
+  }
+}
+
+#[doc(hidden)]
+pub fn update_combo_counters(
+  time: Res<Time>,
+  style: Res<HudStyle>,
+  mut combo_reader: EventReader<ComboEvent>,
+  mut query: Query<(&HudOwner, &mut Text, &mut ComboText)>,
+) {
+  for event in combo_reader.iter() {
+    for (owner, mut text, mut combo_text) in query.iter_mut() {
+      if owner.0 != event.player_id {
+        continue;
+      }
+      match event.kind {
+        ComboEventKind::Dropped => combo_text.linger = Some(style.combo_linger),
+        _ => combo_text.linger = None,
+      }
+      // A single hit isn't a combo
+      if event.hits > 1 {
+        text.sections[0].value = format!("{} Hits\n{} Damage", event.hits, event.damage);
+      }
+    }
+  }
+
+  for (_, mut text, mut combo_text) in query.iter_mut() {
+    if let Some(linger) = combo_text.linger {
+      let linger = linger - time.delta_seconds();
+      if linger <= 0.0 {
+        text.sections[0].value.clear();
+        combo_text.linger = None;
+      } else {
+        combo_text.linger = Some(linger);
+      }
+    }
+  }
+}
+
+#[doc(hidden)]
+pub fn update_callouts(
+  time: Res<Time>,
+  style: Res<HudStyle>,
+  mut counter_hit_reader: EventReader<CounterHitEvent>,
+  mut query: Query<(&HudOwner, &mut Text, &mut CalloutText)>,
+) {
+  for event in counter_hit_reader.iter() {
+    for (owner, mut text, mut callout) in query.iter_mut() {
+      if owner.0 != event.player_id {
+        continue;
+      }
+      let (value, color) = match event.counter_hit {
+        CounterHit::Counter => ("Counter", style.counter_color),
+        CounterHit::Punish => ("Punish", style.punish_color),
+      };
+      text.sections[0].value = value.to_string();
+      text.sections[0].style.color = color;
+      callout.timer = style.callout_duration;
+    }
+  }
+
+  for (_, mut text, mut callout) in query.iter_mut() {
+    if callout.timer > 0.0 {
+      callout.timer -= time.delta_seconds();
+      if callout.timer <= 0.0 {
+        text.sections[0].value.clear();
+      }
+    }
+  }
+}
@@ -1,5 +1,6 @@
 use regex::Regex;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::{
   inputs::{
     PlayerData,
@@ -8,7 +9,8 @@ use crate::{
   },
   character::{
     PlayerId
-  }
+  },
+  round::MatchState
 };
 
 #[derive(Debug)]
@@ -115,7 +117,7 @@ impl CommandMotion {
 
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CommandType {
     FIREBALL,
     R_FIREBALL,
@@ -125,11 +127,13 @@ pub enum CommandType {
     HALF_CIRCLE_FORWARD,
     DASH,
     BACK_DASH,
+    SUPER_JUMP,
     INVITE_HELL
 }
 
 pub fn write_fighter_inputs(
   player_data: Res<PlayerData>,
+  match_state: Res<MatchState>,
   keyboard_input: Res<Input<KeyCode>>, 
   button_input: Res<Input<GamepadButton>>,
   mut input_writer: EventWriter<FighterInputEvent>
@@ -222,6 +226,11 @@ pub fn write_fighter_inputs(
     if f {pressed_byte |= 0b0010_0000}
     if macro_1 {pressed_byte |= 0b0100_0000}
     if macro_2 {pressed_byte |= 0b1000_0000}
+    // Players hold neutral whenever the round isn't being fought
+    if !match_state.is_fighting() {
+      motion = 5;
+      pressed_byte = 0;
+    }
     let button_press = ButtonPress::new(pressed_byte);
     input_writer.send(
       FighterInputEvent::new(
@@ -3,7 +3,8 @@ use crate::{
   inputs::FighterInputBuffer,
   character::{
     PlayerId
-  }
+  },
+  round::RoundConfig
 };
 
 pub struct FighterCharacterPosition {
@@ -48,8 +49,9 @@ pub struct PlayerData {
   pub positions: Vec<FighterCharacterPosition>
 }
 
-impl Default for PlayerData {
-  fn default() -> Self {
+impl PlayerData {
+  /// Create the player data, with both players where the round config starts them in the first round
+  pub fn new(round_config: &RoundConfig) -> Self {
     PlayerData {
       local_devices: vec![
         FighterInputMapper {
@@ -88,14 +90,19 @@ impl Default for PlayerData {
         FighterInputBuffer::new(PlayerId::P2),
       ],
       positions: vec![
-        FighterCharacterPosition::new(PlayerId::P1,Vec3::new(-50.0, 0.0, 0.0)),
-        FighterCharacterPosition::new(PlayerId::P2,Vec3::new(50.0, 0.0, 0.0)),
+        FighterCharacterPosition::new(PlayerId::P1,round_config.starting_position(&PlayerId::P1, 1)),
+        FighterCharacterPosition::new(PlayerId::P2,round_config.starting_position(&PlayerId::P2, 1)),
       ]
     }
   }
-}
 
-impl PlayerData {
+  /// Move both players to where the round config starts them for the given round
+  pub fn set_starting_positions(&mut self, round_config: &RoundConfig, round: u8) {
+    for position in self.positions.iter_mut() {
+      position.set_position(round_config.starting_position(&position.player_id, round));
+    }
+  }
+
   pub fn get_facing_vector(&self, player_id: &PlayerId) -> f32 {
     let p1_x_pos = self.positions[0].get_position().x;
     let p2_x_pos = self.positions[1].get_position().x;
@@ -139,7 +146,7 @@ impl PlayerData {
     let p1 = self.positions[0].get_position();
     let p2 = self.positions[1].get_position();
 
-    return Vec2::new(p1.x+p2.x/2.0, p1.y+p2.y/2.0)
+    return ((p1 + p2) / 2.0).truncate();
   }
 }
 pub struct FighterInputMapper {
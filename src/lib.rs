@@ -19,12 +19,28 @@ pub mod utils;
 pub mod animation;
 /// Hitbox/hurtbox collision
 pub mod collision;
+/// Drawing collision boxes for debugging
+pub mod collision_view;
 /// Character movement and states
 pub mod character;
 /// Camera zoom and movement
 pub mod camera;
 /// Deserializing character attack and animation data
 pub mod character_library;
+/// Stage bounds, spawning stages and parallax backgrounds
+pub mod stage;
+/// Deserializing stage data
+pub mod stage_library;
+/// Health, combo counting and damage scaling
+pub mod combo;
+/// Super meter, meter gain and superflash
+pub mod meter;
+/// Round timer, round phases and match wins
+pub mod round;
+/// Health bars, meter, timer and combo counter
+pub mod hud;
+/// On screen character state and physics info for debugging
+pub mod debug_ui;
 
 use bevy::{
   prelude::*,
@@ -43,8 +59,58 @@ use crate::character::*;
 
 use crate::attacks::*;
 
+use crate::collision::{
+  CollisionEvent,
+  CounterHitEvent,
+  ThrowEvent,
+  detect_collisions,
+  detect_throws,
+  follow_owners
+};
+
 use crate::character_library::*;
 
+use crate::camera::{
+  CameraEffectEvent,
+  manage_camera_effects,
+  trigger_camera_effects
+};
+
+use crate::stage::{
+  StageBounds,
+  scroll_parallax_layers
+};
+
+use crate::stage_library::{
+  StageLibrary,
+  initialize_stage_library
+};
+
+use crate::combo::{
+  ComboEvent,
+  recover_health,
+  update_combos
+};
+
+use crate::meter::{
+  Superflash,
+  SuperflashEvent,
+  gain_meter_on_contact,
+  gain_meter_on_movement,
+  manage_superflash,
+  not_in_superflash
+};
+
+use crate::round::{
+  MatchState,
+  RoundConfig,
+  RoundPhaseEvent,
+  fight_in_progress,
+  initialize_starting_positions,
+  manage_match_state,
+  reset_round
+};
+
 /// Core plugin, handles deserializing data, collision, animation, and physics
 pub struct FighterPlugin;
 
@@ -53,10 +119,24 @@ impl Plugin for FighterPlugin {
     app
       .add_event::<FighterInputEvent>()
       .add_event::<AnimationTransitionEvent>()
+      .add_event::<CollisionEvent>()
+      .add_event::<ThrowEvent>()
+      .add_event::<CounterHitEvent>()
+      .add_event::<ComboEvent>()
+      .add_event::<SuperflashEvent>()
+      .add_event::<RoundPhaseEvent>()
+      .add_event::<CameraEffectEvent>()
       .add_plugin(FrameTimeDiagnosticsPlugin)
       .insert_resource(CharacterLibrary::new())
-      .insert_resource(PlayerData::default())
-      .add_startup_system(initialize_character_library.label(FighterSystemLabels::InitializeCharacterData));
+      .insert_resource(StageLibrary::new())
+      .init_resource::<StageBounds>()
+      .insert_resource(Superflash::default())
+      .insert_resource(MatchState::default())
+      .init_resource::<RoundConfig>()
+      .add_startup_system(initialize_character_library.label(FighterSystemLabels::InitializeCharacterData))
+      .add_startup_system(initialize_stage_library.label(FighterSystemLabels::InitializeStageData))
+      .add_system(scroll_parallax_layers)
+      .add_startup_system(initialize_starting_positions.label(FighterSystemLabels::Setup));
     
     #[cfg(feature = "debug")]
     app
@@ -77,39 +157,169 @@ impl Plugin for FighterPlugin {
             .after(FighterSystemLabels::InputWrite)
         )
         .with_system(
-          manage_character_state
-          .label(FighterSystemLabels::StatusUpdate)
-          .after(FighterSystemLabels::InputRead)
+          manage_superflash
+            .label(FighterSystemLabels::SuperflashUpdate)
+            .after(FighterSystemLabels::InputRead)
         )
+        // Camera effects keep playing through a superflash
         .with_system(
-          manage_character_velocity
-          .label(FighterSystemLabels::PhysicsUpdate)
-          .after(FighterSystemLabels::StatusUpdate)
+          manage_camera_effects
+            .after(FighterSystemLabels::CameraUpdate)
         )
-        .with_system(
-          apply_character_velocity
-            .label(FighterSystemLabels::PhysicsExecute)
-            .after(FighterSystemLabels::PhysicsUpdate)
-        )
-        .with_system(
-          read_animation_transitions
-            .label(FighterSystemLabels::AnimationUpdate)
-            .after(FighterSystemLabels::PhysicsExecute)
+        // Everything but input reading is frozen during a superflash
+        .with_system_set(
+          SystemSet::new()
+            .with_run_criteria(not_in_superflash)
+            .with_system(
+              manage_character_state
+                .label(FighterSystemLabels::StatusUpdate)
+                .after(FighterSystemLabels::InputRead)
+            )
+            .with_system(
+              manage_character_velocity
+                .label(FighterSystemLabels::PhysicsUpdate)
+                .after(FighterSystemLabels::StatusUpdate)
+            )
+            .with_system(
+              apply_character_velocity
+                .label(FighterSystemLabels::PhysicsExecute)
+                .after(FighterSystemLabels::PhysicsUpdate)
+            )
+            .with_system(
+              separate_characters
+                .label(FighterSystemLabels::BodyPush)
+                .after(FighterSystemLabels::PhysicsExecute)
+            )
+            .with_system(
+              read_animation_transitions
+                .label(FighterSystemLabels::AnimationUpdate)
+                .after(FighterSystemLabels::PhysicsExecute)
+            )
+            .with_system(
+              set_character_visibility
+                .label(FighterSystemLabels::VisibilityUpdate)
+                .after(FighterSystemLabels::StatusUpdate)
+            )
+            .with_system(
+              animate_sprite_system
+                .label(FighterSystemLabels::AnimationExecute)
+                .after(FighterSystemLabels::AnimationUpdate)
+            )
+            .with_system(
+              manage_hitboxes
+                .label(FighterSystemLabels::HitboxUpdate)
+                .after(FighterSystemLabels::AnimationUpdate)
+            )
+            .with_system(
+              spawn_hitboxes
+                .label(FighterSystemLabels::HitboxCreation)
+                .after(FighterSystemLabels::HitboxUpdate)
+            )
+            .with_system(
+              move_projectiles
+                .label(FighterSystemLabels::HitboxUpdate)
+                .after(FighterSystemLabels::AnimationUpdate)
+            )
+            .with_system(
+              follow_owners
+                .label(FighterSystemLabels::HitboxUpdate)
+                .after(FighterSystemLabels::BodyPush)
+            )
+            .with_system(
+              update_hurtboxes
+                .label(FighterSystemLabels::HurtboxUpdate)
+                .after(FighterSystemLabels::BodyPush)
+            )
+            .with_system(
+              remove_interrupted_hitboxes
+                .after(FighterSystemLabels::CollisionResolution)
+            )
+            .with_system(
+              manage_projectiles
+                .label(FighterSystemLabels::CollisionResolution)
+                .after(FighterSystemLabels::ProjectileClash)
+            )
+            .with_system(
+              gain_meter_on_movement
+                .label(FighterSystemLabels::MeterUpdate)
+                .after(FighterSystemLabels::StatusUpdate)
+            )
+            .with_system(
+              manage_match_state
+                .label(FighterSystemLabels::RoundUpdate)
+                .after(FighterSystemLabels::ComboUpdate)
+            )
+            .with_system(
+              reset_round
+                .after(FighterSystemLabels::RoundUpdate)
+            )
+            .with_system(
+              trigger_camera_effects
+                .label(FighterSystemLabels::CameraUpdate)
+                .after(FighterSystemLabels::RoundUpdate)
+            )
         )
-        .with_system(
-          animate_sprite_system
-            .label(FighterSystemLabels::AnimationExecute)
-            .after(FighterSystemLabels::AnimationUpdate)
-        )
-        .with_system(
-          manage_hitboxes
-            .label(FighterSystemLabels::HitboxUpdate)
-            .after(FighterSystemLabels::AnimationUpdate)
-        )
-        .with_system(
-          spawn_hitboxes
-            .label(FighterSystemLabels::HitboxCreation)
-            .after(FighterSystemLabels::HitboxUpdate)
+        // Hits, throws and damage only happen while the round is being fought
+        .with_system_set(
+          SystemSet::new()
+            .with_run_criteria(fight_in_progress)
+            .with_system(
+              detect_collisions
+                .label(FighterSystemLabels::CollisionDetection)
+                .after(FighterSystemLabels::HurtboxUpdate)
+                .after(FighterSystemLabels::HitboxUpdate)
+            )
+            .with_system(
+              detect_throws
+                .label(FighterSystemLabels::CollisionDetection)
+                .after(FighterSystemLabels::HurtboxUpdate)
+                .after(FighterSystemLabels::HitboxUpdate)
+            )
+            .with_system(
+              release_throws
+                .label(FighterSystemLabels::CollisionDetection)
+                .after(FighterSystemLabels::StatusUpdate)
+            )
+            // Throws resolve before strikes, so a throw beats a strike landing on the same frame,
+            // and techs are synced once both have been applied
+            .with_system(
+              apply_throws
+                .label(FighterSystemLabels::CollisionResolution)
+                .label(FighterSystemLabels::ThrowResolution)
+                .after(FighterSystemLabels::CollisionDetection)
+            )
+            .with_system(
+              apply_collisions
+                .label(FighterSystemLabels::CollisionResolution)
+                .label(FighterSystemLabels::StrikeResolution)
+                .after(FighterSystemLabels::ThrowResolution)
+            )
+            .with_system(
+              sync_throw_techs
+                .label(FighterSystemLabels::CollisionResolution)
+                .after(FighterSystemLabels::StrikeResolution)
+            )
+            .with_system(
+              update_combos
+                .label(FighterSystemLabels::ComboUpdate)
+                .after(FighterSystemLabels::CollisionResolution)
+            )
+            .with_system(
+              recover_health
+                .after(FighterSystemLabels::ComboUpdate)
+            )
+            // Projectiles clash before their hits are spent, manage_projectiles is the only system that despawns them
+            .with_system(
+              clash_projectiles
+                .label(FighterSystemLabels::CollisionResolution)
+                .label(FighterSystemLabels::ProjectileClash)
+                .after(FighterSystemLabels::CollisionDetection)
+            )
+            .with_system(
+              gain_meter_on_contact
+                .label(FighterSystemLabels::MeterUpdate)
+                .after(FighterSystemLabels::CollisionDetection)
+            )
         )
       );
   }
@@ -123,10 +333,39 @@ pub mod prelude {
       FighterCharacterBundle
     },
     utils::FighterSystemLabels,
+    hud::{
+      FighterHudPlugin,
+      HudOwner,
+      HudStyle
+    },
+    collision_view::{
+      CollisionView,
+      CollisionViewPlugin
+    },
+    debug_ui::{
+      DebugField,
+      DebugOverlayConfig,
+      DebugOverlayPlugin
+    },
     camera::{
       CameraController,
+      CameraEffect,
+      CameraEffectEvent,
+      CameraKeyframe,
       set_camera_scale
     },
     character_library::CharacterLibrary,
+    stage::{
+      SpawnStage,
+      StageBundle
+    },
+    stage_library::StageLibrary,
+    round::{
+      MatchState,
+      RoundConfig,
+      RoundPhase,
+      RoundPhaseEvent,
+      Side
+    },
   };
 }
@@ -0,0 +1,187 @@
+#[cfg(feature = "debug")]
+use bevy_inspector_egui::Inspectable;
+use bevy::{
+  prelude::*,
+  ecs::schedule::ShouldRun
+};
+use serde::{Deserialize, Serialize};
+use crate::{
+  character::{
+    CharacterState,
+    PlayerId
+  },
+  collision::CollisionEvent,
+  constants::MAX_METER,
+  utils::countdown
+};
+
+/// How much meter a character builds from each source
+#[cfg_attr(feature = "debug", derive(Inspectable))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeterGain {
+  /// Meter gained when one of the character's attacks hits
+  pub on_hit: u16,
+  /// Meter gained when one of the character's attacks is blocked
+  pub on_block: u16,
+  /// Meter gained when one of the character's attacks finishes without making contact
+  pub on_whiff: u16,
+  /// Meter gained every frame the character walks, dashes or airdashes forward
+  pub moving_forward: u16,
+}
+
+impl Default for MeterGain {
+  fn default() -> Self {
+    MeterGain {
+      on_hit: 8,
+      on_block: 4,
+      on_whiff: 2,
+      moving_forward: 1,
+    }
+  }
+}
+
+/// Resource gauge spent on super moves
+#[cfg_attr(feature = "debug", derive(Inspectable))]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SuperMeter {
+  current: u16,
+  max: u16,
+  pub gain: MeterGain,
+}
+
+impl Default for SuperMeter {
+  fn default() -> Self {
+    SuperMeter::new(MeterGain::default())
+  }
+}
+
+impl SuperMeter {
+  pub fn new(gain: MeterGain) -> Self {
+    SuperMeter {
+      current: 0,
+      max: MAX_METER,
+      gain
+    }
+  }
+
+  /// Add meter, up to the maximum
+  pub fn add(&mut self, amount: u16) {
+    self.current = self.current.saturating_add(amount).min(self.max);
+  }
+
+  pub fn can_afford(&self, cost: u16) -> bool {
+    return self.current >= cost;
+  }
+
+  /// Spend meter if there is enough of it, returning whether it was spent
+  pub fn spend(&mut self, cost: u16) -> bool {
+    if !self.can_afford(cost) {
+      return false;
+    }
+    self.current -= cost;
+    return true;
+  }
+
+  pub fn get_current(&self) -> u16 {
+    return self.current;
+  }
+
+  pub fn get_max(&self) -> u16 {
+    return self.max;
+  }
+
+  /// Returns the portion of the meter that is filled, from 0 to 1
+  pub fn get_percentage(&self) -> f32 {
+    if self.max == 0 {
+      return 0.0;
+    }
+    return self.current as f32 / self.max as f32;
+  }
+
+  /// Empty the meter
+  pub fn reset(&mut self) {
+    self.current = 0;
+  }
+}
+
+/// Freeze that plays when a super starts, gameplay is paused until it ends
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Superflash {
+  /// Frames until gameplay resumes
+  pub duration: u8,
+  /// Player who started the super
+  pub player_id: Option<PlayerId>,
+}
+
+impl Superflash {
+  pub fn start(&mut self, player_id: PlayerId, duration: u8) {
+    self.duration = duration;
+    self.player_id = Some(player_id);
+  }
+
+  pub fn is_active(&self) -> bool {
+    return self.duration > 0;
+  }
+}
+
+/// Sent when a super starts it's superflash
+pub struct SuperflashEvent {
+  pub player_id: PlayerId,
+  pub duration: u8,
+}
+
+/// Run criteria that pauses gameplay systems during a superflash
+pub fn not_in_superflash(superflash: Res<Superflash>) -> ShouldRun {
+  if superflash.is_active() {
+    ShouldRun::No
+  } else {
+    ShouldRun::Yes
+  }
+}
+
+#[doc(hidden)]
+pub fn manage_superflash(
+  mut superflash: ResMut<Superflash>,
+) {
+  superflash.duration = countdown(superflash.duration);
+  if !superflash.is_active() {
+    superflash.player_id = None;
+  }
+}
+
+/// Build meter for attackers whose hits connect or are blocked
+#[doc(hidden)]
+pub fn gain_meter_on_contact(
+  mut collision_reader: EventReader<CollisionEvent>,
+  mut query: Query<(&PlayerId, &mut SuperMeter)>,
+) {
+  for event in collision_reader.iter() {
+    if event.collision.armored {
+      continue;
+    }
+    for (player_id, mut meter) in query.iter_mut() {
+      if *player_id == event.player_id {
+        let amount = if event.collision.blocked {
+          meter.gain.on_block
+        } else {
+          meter.gain.on_hit
+        };
+        meter.add(amount);
+      }
+    }
+  }
+}
+
+/// Build meter for characters moving towards their opponent
+#[doc(hidden)]
+pub fn gain_meter_on_movement(
+  mut query: Query<(&CharacterState, &mut SuperMeter)>,
+) {
+  for (state, mut meter) in query.iter_mut() {
+    if state.is_moving_forward() {
+      let amount = meter.gain.moving_forward;
+      meter.add(amount);
+    }
+  }
+}
@@ -56,6 +56,14 @@ impl InterpolatedForce {
     self.frames_elapsed += 1;
   }
 
+  pub fn get_current_velocity(&self) -> Vec2 {
+    return self.current_velocity;
+  }
+
+  pub fn get_frames_remaining(&self) -> u8 {
+    return self.duration.saturating_sub(self.frames_elapsed);
+  }
+
   pub fn is_finished(&self) -> bool {
     return self.duration == self.frames_elapsed;
   }
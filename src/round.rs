@@ -0,0 +1,372 @@
+use bevy::{
+  prelude::*,
+  ecs::schedule::ShouldRun
+};
+use crate::{
+  animation::{
+    AnimationTransition,
+    AnimationTransitionEvent
+  },
+  character::{
+    CharacterMovement,
+    CharacterState,
+    PlayerId
+  },
+  collision::{
+    HitGroups,
+    Hitbox,
+    Projectile,
+    Throwbox
+  },
+  combo::{
+    ComboTracker,
+    Health
+  },
+  constants::{
+    DEFAULT_BEST_OF,
+    INTRO_DURATION,
+    KO_DURATION,
+    ROUND_END_DURATION,
+    ROUND_TIME,
+    STARTING_POSITION
+  },
+  inputs::PlayerData,
+  meter::Superflash
+};
+
+/// Side of the stage a character starts the round on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+  Left,
+  Right,
+}
+
+impl Side {
+  pub fn opposite(&self) -> Side {
+    match self {
+      Side::Left => Side::Right,
+      Side::Right => Side::Left,
+    }
+  }
+}
+
+/// Where characters start each round
+#[derive(Debug, Clone, Copy)]
+pub struct RoundConfig {
+  /// Distance from the center of the stage each character starts at
+  pub starting_distance: f32,
+  /// Side player 1 starts the first round on
+  pub p1_side: Side,
+  /// Do the players switch sides every round
+  pub swap_sides: bool,
+}
+
+impl Default for RoundConfig {
+  fn default() -> Self {
+    RoundConfig {
+      starting_distance: STARTING_POSITION,
+      p1_side: Side::Left,
+      swap_sides: false,
+    }
+  }
+}
+
+impl RoundConfig {
+  /// Let a player pick the side they start on, their opponent takes the other side
+  pub fn choose_side(&mut self, player_id: &PlayerId, side: Side) {
+    self.p1_side = match player_id {
+      PlayerId::P1 => side,
+      PlayerId::P2 => side.opposite(),
+    };
+  }
+
+  /// Returns the side a player starts the given round on
+  pub fn get_side(&self, player_id: &PlayerId, round: u8) -> Side {
+    let mut p1_side = self.p1_side;
+    if self.swap_sides && round % 2 == 0 {
+      p1_side = p1_side.opposite();
+    }
+    match player_id {
+      PlayerId::P1 => p1_side,
+      PlayerId::P2 => p1_side.opposite(),
+    }
+  }
+
+  /// Returns where a player stands at the start of the given round
+  pub fn starting_position(&self, player_id: &PlayerId, round: u8) -> Vec3 {
+    match self.get_side(player_id, round) {
+      Side::Left => Vec3::new(-self.starting_distance, 0.0, 0.0),
+      Side::Right => Vec3::new(self.starting_distance, 0.0, 0.0),
+    }
+  }
+}
+
+/// Phases a round moves through, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundPhase {
+  /// Characters are on screen but can't act yet
+  Intro,
+  /// Players are in control and the round timer is running
+  Fight,
+  /// A character ran out of health
+  KO,
+  /// The round timer ran out
+  TimeOver,
+  /// The winner of the round has been decided
+  RoundEnd,
+  /// A player has won enough rounds to win the match
+  MatchEnd,
+}
+
+/// Tracks the current round, the round timer and how many rounds each player has won
+#[derive(Debug, Clone)]
+pub struct MatchState {
+  phase: RoundPhase,
+  /// Frames until the current phase ends, unused during the fight phase
+  phase_duration: u16,
+  /// Frames left on the round timer
+  round_timer: u16,
+  /// Round currently being played, starting at 1
+  round: u8,
+  /// Rounds won by each player
+  wins: [u8; 2],
+  /// Rounds needed to win the match
+  rounds_to_win: u8,
+  /// Winner of the last round, None for a draw
+  round_winner: Option<PlayerId>,
+}
+
+impl Default for MatchState {
+  fn default() -> Self {
+    MatchState::new(DEFAULT_BEST_OF)
+  }
+}
+
+impl MatchState {
+  /// Create a match that is won by taking the majority of `best_of` rounds
+  pub fn new(best_of: u8) -> Self {
+    MatchState {
+      phase: RoundPhase::Intro,
+      phase_duration: INTRO_DURATION,
+      round_timer: ROUND_TIME,
+      round: 1,
+      wins: [0, 0],
+      rounds_to_win: best_of / 2 + 1,
+      round_winner: None,
+    }
+  }
+
+  pub fn get_phase(&self) -> RoundPhase {
+    return self.phase;
+  }
+
+  /// Are players in control of their characters
+  pub fn is_fighting(&self) -> bool {
+    return self.phase == RoundPhase::Fight;
+  }
+
+  pub fn get_round(&self) -> u8 {
+    return self.round;
+  }
+
+  pub fn get_wins(&self, player_id: &PlayerId) -> u8 {
+    return self.wins[player_index(player_id)];
+  }
+
+  pub fn get_round_winner(&self) -> Option<PlayerId> {
+    return self.round_winner;
+  }
+
+  /// Returns the player who won the match, once it's over
+  pub fn get_match_winner(&self) -> Option<PlayerId> {
+    if self.phase != RoundPhase::MatchEnd {
+      return None;
+    }
+    return self.round_winner;
+  }
+
+  /// Whole seconds left on the round timer, as shown on screen
+  pub fn get_seconds_remaining(&self) -> u16 {
+    return (self.round_timer + 59) / 60;
+  }
+
+  /// Advance one frame, returning the new phase if it changed
+  pub fn tick(&mut self) -> Option<RoundPhase> {
+    match self.phase {
+      RoundPhase::Fight => {
+        self.round_timer = self.round_timer.saturating_sub(1);
+        if self.round_timer == 0 {
+          return Some(self.set_phase(RoundPhase::TimeOver));
+        }
+        return None;
+      },
+      RoundPhase::MatchEnd => return None,
+      _ => {
+        self.phase_duration = self.phase_duration.saturating_sub(1);
+        if self.phase_duration > 0 {
+          return None;
+        }
+      }
+    }
+
+    let next = match self.phase {
+      RoundPhase::Intro => RoundPhase::Fight,
+      RoundPhase::KO | RoundPhase::TimeOver => {
+        if let Some(winner) = self.round_winner {
+          self.wins[player_index(&winner)] += 1;
+        }
+        if self.wins.iter().any(|w| *w >= self.rounds_to_win) {
+          RoundPhase::MatchEnd
+        } else {
+          RoundPhase::RoundEnd
+        }
+      },
+      _ => {
+        self.round += 1;
+        self.round_timer = ROUND_TIME;
+        self.round_winner = None;
+        RoundPhase::Intro
+      }
+    };
+    return Some(self.set_phase(next));
+  }
+
+  /// End the round by KO, the player left standing wins
+  pub fn knockout(&mut self, winner: Option<PlayerId>) -> RoundPhase {
+    self.round_winner = winner;
+    return self.set_phase(RoundPhase::KO);
+  }
+
+  /// Decide the winner of a round that timed out, based on remaining health
+  pub fn decide_time_over(&mut self, p1_health: f32, p2_health: f32) {
+    self.round_winner = if p1_health > p2_health {
+      Some(PlayerId::P1)
+    } else if p2_health > p1_health {
+      Some(PlayerId::P2)
+    } else {
+      None
+    };
+  }
+
+  fn set_phase(&mut self, phase: RoundPhase) -> RoundPhase {
+    self.phase = phase;
+    self.phase_duration = match phase {
+      RoundPhase::Intro => INTRO_DURATION,
+      RoundPhase::KO | RoundPhase::TimeOver => KO_DURATION,
+      RoundPhase::RoundEnd => ROUND_END_DURATION,
+      _ => 0
+    };
+    return phase;
+  }
+}
+
+fn player_index(player_id: &PlayerId) -> usize {
+  match player_id {
+    PlayerId::P1 => 0,
+    PlayerId::P2 => 1,
+  }
+}
+
+/// Sent whenever the round moves to a new phase
+pub struct RoundPhaseEvent {
+  pub phase: RoundPhase,
+  pub round: u8,
+  pub winner: Option<PlayerId>,
+}
+
+/// Run criteria that only lets characters hit, throw and damage each other during the fight phase,
+/// while gameplay isn't frozen by a superflash
+pub fn fight_in_progress(match_state: Res<MatchState>, superflash: Res<Superflash>) -> ShouldRun {
+  if match_state.is_fighting() && !superflash.is_active() {
+    ShouldRun::Yes
+  } else {
+    ShouldRun::No
+  }
+}
+
+/// Advance the round timer and phases, ending the round when a character is KO'd
+#[doc(hidden)]
+pub fn manage_match_state(
+  mut match_state: ResMut<MatchState>,
+  query: Query<(&PlayerId, &Health)>,
+  mut phase_writer: EventWriter<RoundPhaseEvent>,
+) {
+  let mut healths = [0.0, 0.0];
+  let mut knocked_out: Vec<PlayerId> = Vec::new();
+  for (player_id, health) in query.iter() {
+    healths[player_index(player_id)] = health.get_percentage();
+    if health.is_empty() {
+      knocked_out.push(*player_id);
+    }
+  }
+
+  let new_phase = if match_state.is_fighting() && !knocked_out.is_empty() {
+    // A double KO is a draw
+    let winner = match knocked_out.as_slice() {
+      [loser] => Some(loser.opponent()),
+      _ => None
+    };
+    Some(match_state.knockout(winner))
+  } else {
+    let new_phase = match_state.tick();
+    if new_phase == Some(RoundPhase::TimeOver) {
+      match_state.decide_time_over(healths[0], healths[1]);
+    }
+    new_phase
+  };
+
+  if let Some(phase) = new_phase {
+    phase_writer.send(RoundPhaseEvent {
+      phase,
+      round: match_state.get_round(),
+      winner: match_state.get_round_winner(),
+    });
+  }
+}
+
+/// Put both characters back at their starting positions when a new round starts
+#[doc(hidden)]
+pub fn reset_round(
+  mut coms: Commands,
+  round_config: Res<RoundConfig>,
+  mut phase_reader: EventReader<RoundPhaseEvent>,
+  mut player_data: ResMut<PlayerData>,
+  mut query: Query<(&PlayerId, &mut Transform, &mut CharacterState, &mut CharacterMovement, &mut Health, &mut ComboTracker, &mut HitGroups)>,
+  box_query: Query<Entity, (Or<(With<Hitbox>, With<Throwbox>)>, Without<Projectile>)>,
+  mut projectile_query: Query<&mut Projectile>,
+  mut transition_writer: EventWriter<AnimationTransitionEvent>,
+) {
+  for event in phase_reader.iter() {
+    if event.phase != RoundPhase::Intro {
+      continue;
+    }
+    for entity in box_query.iter() {
+      coms.entity(entity).despawn_recursive();
+    }
+    for mut projectile in projectile_query.iter_mut() {
+      projectile.destroy();
+    }
+    for (player_id, mut transform, mut state, mut movement, mut health, mut combo_tracker, mut hit_groups) in query.iter_mut() {
+      let position = round_config.starting_position(player_id, event.round);
+      transform.translation = position;
+      player_data.set_position(player_id, position);
+      *state = CharacterState::Idle;
+      movement.velocity = Vec2::ZERO;
+      movement.interpolated_force = None;
+      movement.land();
+      health.reset();
+      combo_tracker.reset();
+      hit_groups.clear();
+      transition_writer.send(AnimationTransitionEvent::new(*player_id, AnimationTransition::ToIdle));
+    }
+  }
+}
+
+/// Build the player data from the round config, so both players start where it places them
+#[doc(hidden)]
+pub fn initialize_starting_positions(
+  mut coms: Commands,
+  round_config: Res<RoundConfig>,
+) {
+  coms.insert_resource(PlayerData::new(&round_config));
+}
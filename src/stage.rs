@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use crate::{
+  camera::{
+    CameraController,
+    CameraZoom
+  },
+  constants::WALL_POSITION,
+  stage_library::Stage
+};
+
+/// Walls and camera limits of the stage currently being fought on
+#[derive(Debug, Clone, Copy)]
+pub struct StageBounds {
+  pub left_wall: f32,
+  pub right_wall: f32,
+  pub width: f32,
+  pub camera_max_y: f32,
+  pub zoom: Option<CameraZoom>,
+}
+
+impl Default for StageBounds {
+  fn default() -> Self {
+    StageBounds {
+      left_wall: -WALL_POSITION,
+      right_wall: WALL_POSITION,
+      width: WALL_POSITION * 2.0,
+      camera_max_y: f32::MAX,
+      zoom: None,
+    }
+  }
+}
+
+impl StageBounds {
+  pub fn from_stage(stage: &Stage) -> Self {
+    StageBounds {
+      left_wall: stage.left_wall,
+      right_wall: stage.right_wall,
+      width: stage.width,
+      camera_max_y: stage.camera_max_y,
+      zoom: stage.zoom,
+    }
+  }
+
+  /// Keep a horizontal position between the walls
+  pub fn clamp(&self, x: f32) -> f32 {
+    x.clamp(self.left_wall, self.right_wall)
+  }
+
+  /// Is the position at a wall, and moving towards it
+  pub fn is_touching_wall(&self, x: f32, velocity_x: f32) -> bool {
+    (x <= self.left_wall && velocity_x < 0.0) || (x >= self.right_wall && velocity_x > 0.0)
+  }
+
+  /// Is the position past either wall
+  pub fn is_outside(&self, x: f32) -> bool {
+    x < self.left_wall || x > self.right_wall
+  }
+}
+
+/// Background sprite that scrolls with the camera
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ParallaxLayer {
+  pub parallax: f32,
+  pub offset: Vec2,
+}
+
+#[derive(Bundle, Default)]
+pub struct StageBundle {
+  pub stage: Stage,
+  pub transform: Transform,
+  pub global_transform: GlobalTransform,
+}
+
+impl StageBundle {
+  pub fn new(stage: Stage) -> Self {
+    StageBundle {
+      transform: Transform::from_xyz(0.0, -stage.floor_height, 0.0),
+      stage,
+      ..Default::default()
+    }
+  }
+}
+
+/// Trait to implement a helper method on Commands to allow easily spawning stages
+pub trait SpawnStage {
+  fn spawn_stage(&mut self, stage: Stage, asset_server: &AssetServer);
+}
+
+impl SpawnStage for Commands<'_, '_> {
+  fn spawn_stage(&mut self, stage: Stage, asset_server: &AssetServer) {
+    self.insert_resource(StageBounds::from_stage(&stage));
+    let layers = stage.layers.clone();
+    self.spawn_bundle(StageBundle::new(stage))
+      .with_children(|parent| {
+        for layer in layers.iter() {
+          parent.spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+              custom_size: layer.size,
+              ..Default::default()
+            },
+            texture: asset_server.load(&format!("sprites/{}", layer.sprite)[..]),
+            transform: Transform::from_xyz(layer.offset.x, layer.offset.y, layer.depth),
+            ..Default::default()
+          })
+          .insert(ParallaxLayer {
+            parallax: layer.parallax,
+            offset: layer.offset,
+          });
+        }
+      });
+  }
+}
+
+/// Move background layers along with the camera based on their parallax factor
+#[doc(hidden)]
+pub fn scroll_parallax_layers(
+  camera_query: Query<&Transform, With<CameraController>>,
+  mut layer_query: Query<(&ParallaxLayer, &mut Transform), Without<CameraController>>,
+) {
+  if let Some(camera_transform) = camera_query.iter().next() {
+    let camera_position = camera_transform.translation.truncate();
+    for (layer, mut transform) in layer_query.iter_mut() {
+      let position = layer.offset + camera_position * layer.parallax;
+      transform.translation.x = position.x;
+      transform.translation.y = position.y;
+    }
+  }
+}
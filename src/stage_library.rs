@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+use bevy::prelude::*;
+use std::{
+  path::Path,
+  collections::HashMap,
+  fs::read_to_string,
+};
+use crate::{
+  camera::CameraZoom,
+  collision::Vec2Serialzed
+};
+
+/// Serialized version of a stage
+#[derive(Deserialize, Serialize)]
+pub struct StageSerialized {
+  pub name: String,
+  pub width: f32,
+  #[serde(default)]
+  pub floor_height: f32,
+  pub left_wall: f32,
+  pub right_wall: f32,
+  pub camera_max_y: f32,
+  #[serde(default)]
+  pub zoom: Option<CameraZoom>,
+  #[serde(default)]
+  pub layers: Vec<StageLayerSerialized>,
+}
+
+/// Serialized version of a background layer
+#[derive(Deserialize, Serialize)]
+pub struct StageLayerSerialized {
+  pub sprite: String,
+  #[serde(default)]
+  pub parallax: f32,
+  #[serde(default)]
+  pub depth: f32,
+  #[serde(default)]
+  pub offset: Vec2Serialzed,
+  #[serde(default)]
+  pub size: Option<Vec2Serialzed>,
+}
+
+/// Playable area and background art characters fight in front of
+#[derive(Component, Debug, Clone, Default)]
+pub struct Stage {
+  pub name: String,
+  /// Total width of the stage, the camera never shows past it
+  pub width: f32,
+  /// Height of the floor in the stage art, the art is lowered so the floor lines up with the ground
+  pub floor_height: f32,
+  pub left_wall: f32,
+  pub right_wall: f32,
+  /// Highest the camera will follow characters
+  pub camera_max_y: f32,
+  /// How far the camera zooms in and out on this stage, the camera's own settings are used if not set
+  pub zoom: Option<CameraZoom>,
+  pub layers: Vec<StageLayer>,
+}
+
+impl Stage {
+  /// Create a stage from it's serialized counterpart
+  pub fn from_serialized(s: StageSerialized) -> Self {
+    Stage {
+      name: s.name,
+      width: s.width,
+      floor_height: s.floor_height,
+      left_wall: s.left_wall,
+      right_wall: s.right_wall,
+      camera_max_y: s.camera_max_y,
+      zoom: s.zoom,
+      layers: s.layers.into_iter().map(StageLayer::from_serialized).collect(),
+    }
+  }
+}
+
+/// A background sprite, scrolling with the camera based on it's parallax factor
+#[derive(Debug, Clone, Default)]
+pub struct StageLayer {
+  /// Path to the layer's image, relative to the sprites folder
+  pub sprite: String,
+  /// How much the layer follows the camera, 0 scrolls with the stage and 1 stays fixed on screen
+  pub parallax: f32,
+  /// Draw order of the layer, characters are drawn at 0
+  pub depth: f32,
+  pub offset: Vec2,
+  /// Size to draw the image at, if it differs from the image itself
+  pub size: Option<Vec2>,
+}
+
+impl StageLayer {
+  pub fn from_serialized(s: StageLayerSerialized) -> Self {
+    StageLayer {
+      sprite: s.sprite,
+      parallax: s.parallax,
+      depth: s.depth,
+      offset: s.offset.to_vec2(),
+      size: s.size.map(|size| size.to_vec2()),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StageLibrary {
+  stages: HashMap<String, Stage>,
+}
+
+impl StageLibrary {
+  pub fn new() -> Self {
+    StageLibrary {
+      stages: HashMap::new()
+    }
+  }
+
+  pub fn load_stage_data(&mut self, stage_name: &str) {
+    let raw_path = format!("./assets/stage_data/{}.json", stage_name);
+    let path = Path::new(&raw_path[..]);
+    if let Ok(raw_string) = read_to_string(path) {
+      let stage_sheet: StageSerialized = from_str(&raw_string[..]).unwrap();
+      self.stages.insert(
+        stage_name.to_string(),
+        Stage::from_serialized(stage_sheet)
+      );
+    }
+  }
+
+  pub fn get_stage(&self, stage_id: &str) -> Option<Stage> {
+    if let Some(stage) = self.stages.get(stage_id) {
+      return Some(stage.clone());
+    } else {
+      return None;
+    }
+  }
+}
+
+#[doc(hidden)]
+pub fn initialize_stage_library(
+  mut stage_library: ResMut<StageLibrary>,
+) {
+  stage_library.load_stage_data("training");
+}
@@ -13,19 +13,35 @@ pub fn countdown(val: u8) -> u8 {
 #[derive(SystemLabel)]
 pub enum FighterSystemLabels {
     InitializeCharacterData,
+    InitializeStageData,
     Setup,
     InputWrite,
     InputRead,
     StatusUpdate,
     PhysicsUpdate,
     PhysicsExecute,
+    BodyPush,
     AnimationUpdate,
     AnimationExecute,
     HitboxUpdate,
-    HitboxCreation
+    HitboxCreation,
+    VisibilityUpdate,
+    HurtboxUpdate,
+    CollisionDetection,
+    CollisionResolution,
+    ThrowResolution,
+    StrikeResolution,
+    ProjectileClash,
+    ComboUpdate,
+    MeterUpdate,
+    SuperflashUpdate,
+    RoundUpdate,
+    CameraUpdate,
+    CollisionViewToggle,
+    CollisionViewCreation
 }
 
-trait MotionGroups {
+pub trait MotionGroups {
     fn y_negative(&self) -> bool;
     fn y_positive(&self) -> bool;
     fn x_positive(&self) -> bool;
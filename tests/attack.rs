@@ -0,0 +1,67 @@
+use bevy_fighter::{
+  attacks::{
+    Armor,
+    Attack,
+    AttackSerialized,
+    FrameRange
+  },
+  character_library::CharacterLibrary,
+  collision::CounterHit
+};
+
+fn throw_attack() -> Attack {
+  let serialized: AttackSerialized = serde_json::from_str(r#"{
+    "name": "throw",
+    "hitbox_events": [],
+    "busy": 20,
+    "throw": {
+      "damage": 10,
+      "position": {"x": 10.0, "y": 0.0},
+      "size": {"x": 20.0, "y": 20.0},
+      "frame": 5,
+      "duration": 30,
+      "release": 25,
+      "tech_window": 7,
+      "force": {"x": 3.0, "y": 0.0}
+    }
+  }"#).unwrap();
+  return Attack::from_serialized(serialized, &CharacterLibrary::new(), "test");
+}
+
+#[test]
+fn counter_hits_default_to_startup_and_recovery() {
+  let attack = throw_attack();
+  assert_eq!(attack.get_counter_hit(0), Some(CounterHit::Counter));
+  assert_eq!(attack.get_counter_hit(4), Some(CounterHit::Counter));
+  assert_eq!(attack.get_counter_hit(5), None);
+  assert_eq!(attack.get_counter_hit(6), None);
+  assert_eq!(attack.get_counter_hit(7), Some(CounterHit::Punish));
+  assert_eq!(attack.get_counter_hit(20), Some(CounterHit::Punish));
+}
+
+#[test]
+fn invulnerability_ranges_are_inclusive() {
+  let attack = Attack {
+    strike_invulnerable: Some(FrameRange {start: 2, end: 6}),
+    ..Default::default()
+  };
+  assert!(!attack.is_strike_invulnerable(1));
+  assert!(attack.is_strike_invulnerable(2));
+  assert!(attack.is_strike_invulnerable(6));
+  assert!(!attack.is_strike_invulnerable(7));
+  assert!(!attack.is_throw_invulnerable(4));
+  assert!(!attack.is_projectile_invulnerable(4));
+}
+
+#[test]
+fn armor_absorbs_hits_inside_its_window() {
+  let mut attack = Attack {
+    armor: Some(Armor {window: FrameRange {start: 3, end: 8}, hits: 1}),
+    ..Default::default()
+  };
+  assert!(!attack.is_armored(2));
+  assert!(attack.is_armored(3));
+  assert!(!attack.is_armored(9));
+  attack.absorb_hit();
+  assert!(!attack.is_armored(3));
+}
@@ -41,3 +41,19 @@ fn hcb_command_motion() {
 fn hcf_command_motion() {
     assert!(MOTIONS[7].check("5544122233366",3));
 }
+
+#[test]
+fn superjump_command_motion() {
+    assert!(MOTIONS[8].check("5552222558",0));
+}
+
+#[test]
+fn superjump_command_motion_outside_window() {
+    assert!(!MOTIONS[8].check("52555555558",0));
+}
+
+#[test]
+fn superjump_command_motion_needs_current_up() {
+    assert!(!MOTIONS[8].check("22255585555555",0));
+    assert!(!MOTIONS[8].check("2225558555555558",0));
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy_fighter::{
+  camera::CameraController,
+  character::PlayerId,
+  inputs::PlayerData,
+  round::RoundConfig,
+  stage::StageBounds
+};
+
+#[test]
+fn mid_point_is_between_players() {
+  let mut player_data = PlayerData::new(&RoundConfig::default());
+  player_data.set_position(&PlayerId::P1, Vec3::new(100.0, 0.0, 0.0));
+  player_data.set_position(&PlayerId::P2, Vec3::new(200.0, 50.0, 0.0));
+  assert_eq!(player_data.get_mid_point(), Vec2::new(150.0, 25.0));
+}
+
+#[test]
+fn camera_stays_inside_stage() {
+  let controller = CameraController::default();
+  let bounds = StageBounds::default();
+  let edge = bounds.width / 2.0 - 200.0;
+  let target = controller.get_target_position(Vec2::new(10000.0, 0.0), 200.0, &bounds);
+  assert_eq!(target.x, edge);
+}
+
+#[test]
+fn camera_stays_inside_off_center_stage() {
+  let controller = CameraController::default();
+  let bounds = StageBounds {
+    left_wall: -400.0,
+    right_wall: 800.0,
+    width: 1400.0,
+    ..Default::default()
+  };
+  let left = controller.get_target_position(Vec2::new(-10000.0, 0.0), 200.0, &bounds);
+  let right = controller.get_target_position(Vec2::new(10000.0, 0.0), 200.0, &bounds);
+  assert_eq!(left.x, -300.0);
+  assert_eq!(right.x, 700.0);
+}
+
+#[test]
+fn small_jumps_dont_move_camera() {
+  let controller = CameraController::default();
+  let bounds = StageBounds::default();
+  let grounded = controller.get_target_position(Vec2::ZERO, 200.0, &bounds);
+  let jumping = controller.get_target_position(Vec2::new(0.0, controller.y_deadzone), 200.0, &bounds);
+  assert_eq!(grounded, jumping);
+}
+
+#[test]
+fn shakes_are_the_same_every_time() {
+  let mut a = CameraController::default();
+  let mut b = CameraController::default();
+  a.shake(8.0, 20.0, 0.9, 30);
+  b.shake(8.0, 20.0, 0.9, 30);
+  for _ in 0..10 {
+    a.tick_effects();
+    b.tick_effects();
+  }
+  assert_eq!(a.get_shake_offset(), b.get_shake_offset());
+}
+
+#[test]
+fn effects_end_after_their_duration() {
+  let mut controller = CameraController::default();
+  controller.punch_in(Vec2::ZERO, 0.3, 5);
+  for _ in 0..5 {
+    controller.tick_effects();
+  }
+  assert!(!controller.has_effects());
+}
+
+#[test]
+fn punch_in_leaves_no_trace_on_the_zoom() {
+  let mut controller = CameraController::default();
+  let (_, before) = controller.get_view(Vec2::ZERO);
+  controller.punch_in(Vec2::new(100.0, 0.0), 0.3, 6);
+  let (position, scale) = controller.get_view(Vec2::ZERO);
+  assert_eq!(position, Vec2::new(100.0, 0.0));
+  assert_eq!(scale, 0.3);
+  for _ in 0..6 {
+    controller.tick_effects();
+  }
+  assert_eq!(controller.get_view(Vec2::ZERO), (Vec2::ZERO, before));
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy_fighter::{
+  character::{
+    CharacterAttacks,
+    CharacterMovement,
+    CharacterState,
+    PlayerId
+  },
+  character_library::CharacterLibrary,
+  collision::{
+    Collision,
+    Hitbox
+  },
+  inputs::FighterInputBuffer,
+  stage::StageBounds
+};
+
+#[test]
+fn airdash_lands_with_airdash_recovery() {
+  let mut buffer = FighterInputBuffer::new(PlayerId::P1);
+  let mut movement = CharacterMovement::default();
+  let mut attacks = CharacterAttacks::default();
+  let name = Name::new("test");
+  let library = CharacterLibrary::new();
+  let bounds = StageBounds::default();
+  let airborne = Vec3::new(0.0, 100.0, 0.0);
+
+  let mut state = CharacterState::AirDashing {busy: 0, duration: 1, velocity: Vec2::X};
+  state.update(&mut buffer, &mut movement, &mut attacks, &name, &library, airborne, &bounds);
+  assert_eq!(state, CharacterState::Falling);
+
+  state.update(&mut buffer, &mut movement, &mut attacks, &name, &library, airborne, &bounds);
+  state.update(&mut buffer, &mut movement, &mut attacks, &name, &library, Vec3::ZERO, &bounds);
+  match state {
+    CharacterState::LandingRecovery {duration} => assert_eq!(duration, movement.landing.airdash),
+    _ => panic!("expected landing recovery, got {:?}", state)
+  }
+}
+
+#[test]
+fn jump_lands_with_jump_recovery() {
+  let mut buffer = FighterInputBuffer::new(PlayerId::P1);
+  let mut movement = CharacterMovement::default();
+  let mut attacks = CharacterAttacks::default();
+  let name = Name::new("test");
+  let library = CharacterLibrary::new();
+  let bounds = StageBounds::default();
+
+  let mut state = CharacterState::Falling;
+  state.update(&mut buffer, &mut movement, &mut attacks, &name, &library, Vec3::ZERO, &bounds);
+  match state {
+    CharacterState::LandingRecovery {duration} => assert_eq!(duration, movement.landing.jump),
+    _ => panic!("expected landing recovery, got {:?}", state)
+  }
+}
+
+#[test]
+fn throws_do_not_land_airborne_characters() {
+  let falling = CharacterState::Falling;
+  assert!(!CharacterState::Thrown {duration: 30, tech: 7, airborne: true}.has_landed(&falling));
+  assert!(CharacterState::Idle.has_landed(&falling));
+  assert!(!CharacterState::Idle.has_landed(&CharacterState::Idle));
+}
+
+#[test]
+fn back_leap_does_not_land_while_rising() {
+  let mut movement = CharacterMovement::default();
+  let state = CharacterState::BackLeaping {busy: 10};
+  movement.velocity = Vec2::new(-4.0, 2.0);
+  assert_eq!(state.from_backleaping(&movement, Vec3::ZERO), CharacterState::BackLeaping {busy: 10});
+  movement.velocity = Vec2::new(-4.0, -2.0);
+  assert_eq!(state.from_backleaping(&movement, Vec3::ZERO), CharacterState::BackLeapLanding {duration: 10});
+}
+
+#[test]
+fn air_thrown_characters_are_released_into_a_juggle() {
+  let mut movement = CharacterMovement::default();
+  let release = Collision::new(Hitbox::default(), false);
+
+  let air_thrown = CharacterState::Thrown {duration: 30, tech: 0, airborne: true};
+  assert!(air_thrown.get_airborne());
+  assert_eq!(air_thrown.take_hit(&release, &mut movement), CharacterState::Juggle {duration: 0, hits: 0, wall_bounce: false, ground_bounce: false, hard_knockdown: false});
+
+  let grounded_thrown = CharacterState::Thrown {duration: 30, tech: 0, airborne: false};
+  assert_eq!(grounded_thrown.take_hit(&release, &mut movement), CharacterState::Hitstun {duration: 0, hits: 0});
+}
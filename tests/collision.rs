@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use bevy::prelude::*;
+use bevy_fighter::{
+  character::PlayerId,
+  character_library::CharacterLibrary,
+  collision::{
+    boxes_overlap,
+    Hitbox,
+    HitboxEvent,
+    HitboxEventSerialized,
+    push_apart,
+    Pushbox
+  },
+  stage::StageBounds
+};
+
+/// Builds a hitbox that can hit each player up to `max_hits` times, `rehit_interval` frames apart
+fn multi_hitbox(rehit_interval: u8, max_hits: u8) -> Hitbox {
+  let mut library = CharacterLibrary::new();
+  library.add_hitboxes(HashMap::from([("test_hit".to_string(), Hitbox::default())]));
+  let serialized: HitboxEventSerialized = serde_json::from_str(&format!(r#"{{
+    "hitbox": "hit",
+    "position": {{"x": 0.0, "y": 0.0}},
+    "size": {{"x": 10.0, "y": 10.0}},
+    "frame": 0,
+    "rehit_interval": {},
+    "max_hits": {}
+  }}"#, rehit_interval, max_hits)).unwrap();
+  return HitboxEvent::from_serialized(serialized, &library, "test").hitbox;
+}
+
+#[test]
+fn overlapping_boxes() {
+  assert!(boxes_overlap(Vec2::ZERO, Vec2::new(10.0, 10.0), Vec2::new(8.0, 0.0), Vec2::new(10.0, 10.0)));
+}
+
+#[test]
+fn touching_boxes_do_not_overlap() {
+  assert!(!boxes_overlap(Vec2::ZERO, Vec2::new(10.0, 10.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)));
+}
+
+#[test]
+fn separated_boxes_do_not_overlap() {
+  assert!(!boxes_overlap(Vec2::ZERO, Vec2::new(10.0, 10.0), Vec2::new(0.0, 30.0), Vec2::new(10.0, 10.0)));
+}
+
+#[test]
+fn hitbox_waits_for_rehit_interval() {
+  let mut hitbox = multi_hitbox(3, 5);
+  hitbox.register_hit(&PlayerId::P2, false);
+  for _ in 0..2 {
+    hitbox.tick();
+    assert!(!hitbox.can_hit_player(&PlayerId::P2));
+  }
+  hitbox.tick();
+  assert!(hitbox.can_hit_player(&PlayerId::P2));
+}
+
+#[test]
+fn hitbox_stops_at_max_hits() {
+  let mut hitbox = multi_hitbox(0, 2);
+  hitbox.register_hit(&PlayerId::P2, false);
+  assert!(hitbox.can_hit_player(&PlayerId::P2));
+  hitbox.register_hit(&PlayerId::P2, false);
+  assert!(!hitbox.can_hit_player(&PlayerId::P2));
+}
+
+#[test]
+fn hitbox_tracks_victims_separately() {
+  let mut hitbox = multi_hitbox(3, 1);
+  hitbox.register_hit(&PlayerId::P2, true);
+  assert!(hitbox.has_hit_player(&PlayerId::P2));
+  assert!(!hitbox.can_hit_player(&PlayerId::P2));
+  assert!(!hitbox.has_hit_player(&PlayerId::P1));
+  assert!(hitbox.can_hit_player(&PlayerId::P1));
+}
+
+#[test]
+fn pushboxes_overlap_horizontally() {
+  let pushbox = Pushbox {offset: Vec2::ZERO, size: Vec2::new(50.0, 100.0)};
+  assert_eq!(pushbox.get_overlap(Vec2::ZERO, &pushbox, Vec2::new(30.0, 0.0)), 20.0);
+  assert_eq!(pushbox.get_overlap(Vec2::ZERO, &pushbox, Vec2::new(60.0, 0.0)), 0.0);
+}
+
+#[test]
+fn characters_are_pushed_apart_evenly() {
+  assert_eq!(push_apart(0.0, 30.0, 20.0, &StageBounds::default()), (-10.0, 40.0));
+}
+
+#[test]
+fn characters_in_the_corner_push_their_opponent() {
+  let bounds = StageBounds::default();
+  let (a, b) = push_apart(bounds.left_wall, bounds.left_wall + 30.0, 20.0, &bounds);
+  assert_eq!(a, bounds.left_wall);
+  assert_eq!(b, bounds.left_wall + 50.0);
+}
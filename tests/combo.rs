@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use bevy_fighter::{
+  character::{
+    CharacterState,
+    PlayerId
+  },
+  collision::{
+    Collision,
+    CollisionEvent,
+    Hitbox,
+    HitboxSerialized
+  },
+  combo::{
+    update_combos,
+    ComboEvent,
+    ComboTracker,
+    Health
+  },
+  constants::HEALTH_RECOVERY_DELAY
+};
+
+fn blocked_hit(chip: bool) -> CollisionEvent {
+  let serialized: HitboxSerialized = serde_json::from_str(&format!(r#"{{
+    "name": "hit",
+    "attack_level": 2,
+    "damage": 40,
+    "proration": 1.0,
+    "force": {{"x": 2.0, "y": 0.0}},
+    "air_blockable": true,
+    "property": "Mid",
+    "duration": 3,
+    "chip": {},
+    "projectile": false
+  }}"#, chip)).unwrap();
+  return CollisionEvent {
+    collision: Collision::new(Hitbox::from_serialized(serialized), true),
+    player_id: PlayerId::P1,
+    recieving_player_id: PlayerId::P2,
+  };
+}
+
+#[test]
+fn first_hit_is_unscaled() {
+  let mut tracker = ComboTracker::default();
+  assert_eq!(tracker.register_hit(100, 0.5), 100);
+}
+
+#[test]
+fn starter_proration_scales_following_hits() {
+  let mut tracker = ComboTracker::default();
+  tracker.register_hit(100, 0.5);
+  assert_eq!(tracker.register_hit(100, 1.0), 50);
+  assert_eq!(tracker.get_hits(), 2);
+  assert_eq!(tracker.get_damage(), 150);
+}
+
+#[test]
+fn scaling_has_a_floor() {
+  let mut tracker = ComboTracker::default();
+  for _ in 0..50 {
+    tracker.register_hit(100, 1.0);
+  }
+  assert!(tracker.get_scaling() >= 0.2);
+}
+
+#[test]
+fn health_does_not_underflow() {
+  let mut health = Health::new(100);
+  health.take_damage(250);
+  assert!(health.is_empty());
+}
+
+#[test]
+fn recoverable_health_comes_back_after_a_delay() {
+  let mut health = Health::new(100);
+  health.take_damage(40);
+  assert_eq!(health.current, 60);
+  assert_eq!(health.recoverable, 20);
+
+  for _ in 0..HEALTH_RECOVERY_DELAY {
+    health.recover();
+  }
+  assert_eq!(health.current, 60);
+  for _ in 0..100 {
+    health.recover();
+  }
+  assert_eq!(health.current, 80);
+  assert_eq!(health.recoverable, 0);
+}
+
+#[test]
+fn knocked_out_characters_recover_nothing() {
+  let mut health = Health::new(100);
+  health.take_damage(40);
+  health.take_damage(100);
+  assert_eq!(health.recoverable, 0);
+}
+
+#[test]
+fn blocked_hits_only_deal_chip_damage() {
+  let mut world = World::new();
+  world.insert_resource(Events::<CollisionEvent>::default());
+  world.insert_resource(Events::<ComboEvent>::default());
+  let defender = world.spawn()
+    .insert(PlayerId::P2)
+    .insert(CharacterState::Blockstun {duration: 10})
+    .insert(ComboTracker::default())
+    .insert(Health::new(100))
+    .id();
+  let mut stage = SystemStage::single_threaded().with_system(update_combos);
+
+  world.get_resource_mut::<Events<CollisionEvent>>().unwrap().send(blocked_hit(false));
+  stage.run(&mut world);
+  assert_eq!(world.get::<Health>(defender).unwrap().current, 100);
+
+  world.get_resource_mut::<Events<CollisionEvent>>().unwrap().send(blocked_hit(true));
+  stage.run(&mut world);
+  assert_eq!(world.get::<Health>(defender).unwrap().current, 90);
+  assert!(!world.get::<ComboTracker>(defender).unwrap().is_active());
+}
@@ -0,0 +1,18 @@
+use bevy_fighter::meter::{MeterGain, SuperMeter};
+
+#[test]
+fn meter_does_not_exceed_max() {
+  let mut meter = SuperMeter::new(MeterGain::default());
+  meter.add(u16::MAX);
+  assert_eq!(meter.get_current(), meter.get_max());
+}
+
+#[test]
+fn spending_requires_enough_meter() {
+  let mut meter = SuperMeter::new(MeterGain::default());
+  meter.add(50);
+  assert!(!meter.spend(100));
+  assert_eq!(meter.get_current(), 50);
+  assert!(meter.spend(50));
+  assert_eq!(meter.get_current(), 0);
+}
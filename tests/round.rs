@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+use bevy_fighter::{
+  character::{
+    CharacterState,
+    PlayerId
+  },
+  collision::{
+    Collision,
+    CollisionEvent,
+    Hitbox,
+    HitboxSerialized
+  },
+  combo::{
+    update_combos,
+    ComboEvent,
+    ComboTracker,
+    Health
+  },
+  constants::{
+    INTRO_DURATION,
+    KO_DURATION
+  },
+  inputs::PlayerData,
+  meter::Superflash,
+  round::{
+    fight_in_progress,
+    initialize_starting_positions,
+    MatchState,
+    RoundConfig,
+    RoundPhase,
+    Side
+  }
+};
+
+fn tick_frames(match_state: &mut MatchState, frames: u16) -> Option<RoundPhase> {
+  let mut last = None;
+  for _ in 0..frames {
+    if let Some(phase) = match_state.tick() {
+      last = Some(phase);
+    }
+  }
+  return last;
+}
+
+#[test]
+fn intro_leads_to_fight() {
+  let mut match_state = MatchState::new(3);
+  assert_eq!(tick_frames(&mut match_state, INTRO_DURATION), Some(RoundPhase::Fight));
+  assert!(match_state.is_fighting());
+}
+
+#[test]
+fn winning_the_majority_of_rounds_ends_the_match() {
+  let mut match_state = MatchState::new(3);
+  for _ in 0..2 {
+    while !match_state.is_fighting() {
+      match_state.tick();
+    }
+    match_state.knockout(Some(PlayerId::P1));
+    tick_frames(&mut match_state, KO_DURATION);
+  }
+  assert_eq!(match_state.get_wins(&PlayerId::P1), 2);
+  assert_eq!(match_state.get_match_winner(), Some(PlayerId::P1));
+}
+
+#[test]
+fn players_start_on_opposite_sides() {
+  let config = RoundConfig::default();
+  let p1 = config.starting_position(&PlayerId::P1, 1);
+  let p2 = config.starting_position(&PlayerId::P2, 1);
+  assert!(p1.x < 0.0);
+  assert_eq!(p1.x, -p2.x);
+}
+
+#[test]
+fn sides_swap_every_round() {
+  let config = RoundConfig {swap_sides: true, ..Default::default()};
+  assert_eq!(config.get_side(&PlayerId::P1, 1), Side::Left);
+  assert_eq!(config.get_side(&PlayerId::P1, 2), Side::Right);
+  assert_eq!(config.get_side(&PlayerId::P1, 3), Side::Left);
+}
+
+#[test]
+fn choosing_a_side_moves_the_opponent() {
+  let mut config = RoundConfig::default();
+  config.choose_side(&PlayerId::P2, Side::Left);
+  assert_eq!(config.get_side(&PlayerId::P1, 1), Side::Right);
+}
+
+#[test]
+fn player_data_starts_where_a_custom_config_places_players() {
+  let mut world = World::new();
+  world.insert_resource(RoundConfig {starting_distance: 120.0, p1_side: Side::Right, ..Default::default()});
+  let mut stage = SystemStage::single_threaded().with_system(initialize_starting_positions);
+  stage.run(&mut world);
+
+  let mut player_data = world.get_resource_mut::<PlayerData>().unwrap();
+  assert_eq!(player_data.get_position(&PlayerId::P1), Vec3::new(120.0, 0.0, 0.0));
+  assert_eq!(player_data.get_position(&PlayerId::P2), Vec3::new(-120.0, 0.0, 0.0));
+}
+
+fn send_hit(world: &mut World) {
+  let serialized: HitboxSerialized = serde_json::from_str(r#"{
+    "name": "hit",
+    "attack_level": 2,
+    "damage": 40,
+    "proration": 1.0,
+    "force": {"x": 2.0, "y": 0.0},
+    "air_blockable": true,
+    "property": "Mid",
+    "duration": 3,
+    "chip": false,
+    "projectile": false
+  }"#).unwrap();
+  world.get_resource_mut::<Events<CollisionEvent>>().unwrap().send(CollisionEvent {
+    collision: Collision::new(Hitbox::from_serialized(serialized), false),
+    player_id: PlayerId::P1,
+    recieving_player_id: PlayerId::P2,
+  });
+}
+
+#[test]
+fn health_only_changes_during_the_fight() {
+  let mut world = World::new();
+  world.insert_resource(MatchState::new(3));
+  world.insert_resource(Superflash::default());
+  world.insert_resource(Events::<CollisionEvent>::default());
+  world.insert_resource(Events::<ComboEvent>::default());
+  let defender = world.spawn()
+    .insert(PlayerId::P2)
+    .insert(CharacterState::Hitstun {duration: 10, hits: 1})
+    .insert(ComboTracker::default())
+    .insert(Health::new(100))
+    .id();
+  let mut stage = SystemStage::single_threaded().with_system_set(
+    SystemSet::new()
+      .with_run_criteria(fight_in_progress)
+      .with_system(update_combos)
+  );
+
+  // Intro
+  send_hit(&mut world);
+  stage.run(&mut world);
+  assert_eq!(world.get::<Health>(defender).unwrap().current, 100);
+
+  // KO
+  let mut match_state = world.get_resource_mut::<MatchState>().unwrap();
+  tick_frames(&mut match_state, INTRO_DURATION);
+  match_state.knockout(Some(PlayerId::P1));
+  send_hit(&mut world);
+  stage.run(&mut world);
+  assert_eq!(world.get::<Health>(defender).unwrap().current, 100);
+
+  // Next round's fight
+  let mut match_state = world.get_resource_mut::<MatchState>().unwrap();
+  while !match_state.is_fighting() {
+    match_state.tick();
+  }
+  // Hits sent outside the fight are dropped along with the frames they were sent on
+  let mut collision_events = world.get_resource_mut::<Events<CollisionEvent>>().unwrap();
+  collision_events.update();
+  collision_events.update();
+  send_hit(&mut world);
+  stage.run(&mut world);
+  assert_eq!(world.get::<Health>(defender).unwrap().current, 60);
+}
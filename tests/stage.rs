@@ -0,0 +1,70 @@
+use bevy_fighter::{
+  stage::StageBounds,
+  stage_library::{
+    Stage,
+    StageSerialized
+  }
+};
+
+fn bounds() -> StageBounds {
+  StageBounds {
+    left_wall: -500.0,
+    right_wall: 700.0,
+    width: 1400.0,
+    camera_max_y: 400.0,
+    zoom: None,
+  }
+}
+
+#[test]
+fn clamp_keeps_positions_between_walls() {
+  let bounds = bounds();
+  assert_eq!(bounds.clamp(-900.0), -500.0);
+  assert_eq!(bounds.clamp(900.0), 700.0);
+  assert_eq!(bounds.clamp(100.0), 100.0);
+}
+
+#[test]
+fn touching_wall_requires_moving_into_it() {
+  let bounds = bounds();
+  assert!(bounds.is_touching_wall(-500.0, -1.0));
+  assert!(bounds.is_touching_wall(700.0, 1.0));
+  assert!(!bounds.is_touching_wall(-500.0, 1.0));
+  assert!(!bounds.is_touching_wall(100.0, -1.0));
+}
+
+#[test]
+fn outside_is_past_either_wall() {
+  let bounds = bounds();
+  assert!(bounds.is_outside(-501.0));
+  assert!(bounds.is_outside(701.0));
+  assert!(!bounds.is_outside(700.0));
+}
+
+#[test]
+fn stage_deserializes_with_defaults() {
+  let raw = r#"{
+    "name": "test",
+    "width": 1400.0,
+    "left_wall": -500.0,
+    "right_wall": 700.0,
+    "camera_max_y": 400.0
+  }"#;
+  let stage = Stage::from_serialized(serde_json::from_str::<StageSerialized>(raw).unwrap());
+  assert_eq!(stage.floor_height, 0.0);
+  assert!(stage.zoom.is_none());
+  assert!(stage.layers.is_empty());
+
+  let bounds = StageBounds::from_stage(&stage);
+  assert_eq!(bounds.left_wall, -500.0);
+  assert_eq!(bounds.right_wall, 700.0);
+}
+
+#[test]
+fn training_stage_deserializes() {
+  let raw = std::fs::read_to_string("./assets/stage_data/training.json").unwrap();
+  let stage = Stage::from_serialized(serde_json::from_str::<StageSerialized>(&raw).unwrap());
+  assert_eq!(stage.name, "training");
+  assert!(stage.zoom.is_some());
+  assert_eq!(stage.layers.len(), 2);
+}